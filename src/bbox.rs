@@ -0,0 +1,512 @@
+use crate::pie_chart::{PieChart, Sunburst};
+use crate::plot::Plot;
+use crate::tab::Tab;
+use crate::transform::Affine;
+use std::f64::consts::PI;
+use svg::node::element::path::{Command, Data, Position};
+use svg::node::Node;
+
+/// An axis aligned rectangle, given by its minimum and maximum corners.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rect {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl Rect {
+    /// Create a rectangle from two opposite corners.
+    pub fn new(a: (f64, f64), b: (f64, f64)) -> Self {
+        Rect {
+            min_x: a.0.min(b.0),
+            min_y: a.1.min(b.1),
+            max_x: a.0.max(b.0),
+            max_y: a.1.max(b.1),
+        }
+    }
+
+    /// Create a rectangle from its top left corner and its size.
+    pub fn from_size(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Rect::new((x, y), (x + width, y + height))
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        (
+            (self.min_x + self.max_x) / 2.0,
+            (self.min_y + self.max_y) / 2.0,
+        )
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+
+    /// The rectangle grown by margin on every side.
+    pub fn expand(&self, margin: f64) -> Rect {
+        Rect {
+            min_x: self.min_x - margin,
+            min_y: self.min_y - margin,
+            max_x: self.max_x + margin,
+            max_y: self.max_y + margin,
+        }
+    }
+
+    /// The bounds of this rectangle after the transform is applied to it.
+    pub fn transformed(&self, transform: &Affine) -> Rect {
+        let (min, max) = transform.apply_bounds((self.min_x, self.min_y), (self.max_x, self.max_y));
+        Rect::new(min, max)
+    }
+}
+
+/// The extent of a shape, in the coordinates of its parent.
+///
+/// The stroke width is not taken into account, and text is estimated from its length.
+pub trait BoundingBox {
+    /// The bounding box, none if nothing is drawn.
+    fn bounding_box(&self) -> Option<Rect>;
+}
+
+impl<T: Node> BoundingBox for T {
+    fn bounding_box(&self) -> Option<Rect> {
+        bounds(self)
+    }
+}
+
+impl BoundingBox for Tab {
+    fn bounding_box(&self) -> Option<Rect> {
+        bounds(&self.svg())
+    }
+}
+
+impl BoundingBox for PieChart {
+    fn bounding_box(&self) -> Option<Rect> {
+        bounds(&self.svg())
+    }
+}
+
+impl BoundingBox for Sunburst {
+    fn bounding_box(&self) -> Option<Rect> {
+        bounds(&self.svg())
+    }
+}
+
+impl BoundingBox for Plot {
+    fn bounding_box(&self) -> Option<Rect> {
+        bounds(&self.svg())
+    }
+}
+
+/// The bounding box of a node and its children, in the coordinates of its parent.
+///
+/// Nodes with a transform that can't be parsed are skipped, along with their children.
+pub fn bounds(node: &dyn Node) -> Option<Rect> {
+    let mut collector = Collector::default();
+    collector.node(node, Affine::IDENTITY);
+    collector.rect
+}
+
+/// Accumulates the extent of shapes, which are transformed before they are added.
+#[derive(Default)]
+struct Collector {
+    rect: Option<Rect>,
+}
+
+/// The font size used for text without one, the browser default.
+const DEFAULT_FONT_SIZE: f64 = 16.0;
+
+fn number(node: &dyn Node, name: &str) -> Option<f64> {
+    let value = node.get_attributes()?.get(name)?.to_string();
+    value.trim().trim_end_matches("px").parse().ok()
+}
+
+fn attribute(node: &dyn Node, name: &str) -> Option<String> {
+    node.get_attributes()?.get(name).map(|v| v.to_string())
+}
+
+impl Collector {
+    fn point(&mut self, transform: &Affine, p: (f64, f64)) {
+        let (x, y) = transform.apply(p);
+        if !x.is_finite() || !y.is_finite() {
+            return;
+        }
+        let r = Rect::new((x, y), (x, y));
+        self.rect = Some(self.rect.map_or(r, |c| c.union(&r)));
+    }
+
+    fn node(&mut self, node: &dyn Node, parent: Affine) {
+        if node.is_bare() {
+            return;
+        }
+        let transform = match attribute(node, "transform").map(|t| Affine::parse(&t)) {
+            Some(Ok(t)) => parent * t,
+            // Where the node ends up is unknown, so it does not count towards the bounds.
+            Some(Err(_)) => return,
+            None => parent,
+        };
+        let num = |name: &str| number(node, name).unwrap_or(0.0);
+        match node.get_name() {
+            // Definitions are only drawn where they are referenced.
+            "defs" | "linearGradient" | "radialGradient" | "pattern" | "marker" | "clipPath"
+            | "mask" | "symbol" | "title" | "desc" | "style" | "script" => {}
+            "path" => {
+                if let Some(d) = attribute(node, "d").and_then(|d| Data::parse(&d).ok()) {
+                    self.path(&d, &transform);
+                }
+            }
+            "rect" => {
+                let (x, y) = (num("x"), num("y"));
+                let (w, h) = (num("width"), num("height"));
+                for p in [(x, y), (x + w, y), (x + w, y + h), (x, y + h)] {
+                    self.point(&transform, p);
+                }
+            }
+            "circle" => {
+                let r = num("r");
+                self.ellipse(&transform, (num("cx"), num("cy")), r, r, 0.0, 0.0, 2.0 * PI);
+            }
+            "ellipse" => {
+                let (rx, ry) = (num("rx"), num("ry"));
+                self.ellipse(
+                    &transform,
+                    (num("cx"), num("cy")),
+                    rx,
+                    ry,
+                    0.0,
+                    0.0,
+                    2.0 * PI,
+                );
+            }
+            "line" => {
+                self.point(&transform, (num("x1"), num("y1")));
+                self.point(&transform, (num("x2"), num("y2")));
+            }
+            "polyline" | "polygon" => {
+                let points = attribute(node, "points").unwrap_or_default();
+                let values: Vec<f64> = points
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter_map(|v| v.parse().ok())
+                    .collect();
+                for p in values.chunks_exact(2) {
+                    self.point(&transform, (p[0], p[1]));
+                }
+            }
+            "text" => self.text(node, &transform),
+            _ => {
+                for child in node.get_children().into_iter().flatten() {
+                    self.node(child.as_ref(), transform);
+                }
+            }
+        }
+    }
+
+    /// Text is estimated, like the legend does, from its length and font size.
+    fn text(&mut self, node: &dyn Node, transform: &Affine) {
+        let content: String = node
+            .get_children()
+            .into_iter()
+            .flatten()
+            .filter(|c| c.is_bare())
+            .map(|c| c.to_string())
+            .collect();
+        let font_size = number(node, "font-size").unwrap_or(DEFAULT_FONT_SIZE);
+        let width = content.chars().count() as f64 * font_size * 0.6;
+        let x = number(node, "x").unwrap_or(0.0);
+        let y = number(node, "y").unwrap_or(0.0);
+        let left = match attribute(node, "text-anchor").as_deref() {
+            Some("middle") => x - width / 2.0,
+            Some("end") => x - width,
+            _ => x,
+        };
+        let top = match attribute(node, "dominant-baseline").as_deref() {
+            Some("middle") | Some("central") => y - font_size / 2.0,
+            Some("hanging") | Some("text-before-edge") => y,
+            _ => y - font_size,
+        };
+        for p in [
+            (left, top),
+            (left + width, top),
+            (left + width, top + font_size),
+            (left, top + font_size),
+        ] {
+            self.point(transform, p);
+        }
+    }
+
+    fn cubic(&mut self, transform: &Affine, p: [(f64, f64); 4]) {
+        let p = p.map(|q| transform.apply(q));
+        self.point(&Affine::IDENTITY, p[0]);
+        self.point(&Affine::IDENTITY, p[3]);
+        // Extremes are where the derivative of either coordinate is zero.
+        let axis = |i: usize| p.map(|q| if i == 0 { q.0 } else { q.1 });
+        for i in 0..2 {
+            let [a, b, c, d] = axis(i);
+            // Derivative is a t^2 + b t + c, divided by 3.
+            let qa = -a + 3.0 * b - 3.0 * c + d;
+            let qb = 2.0 * (a - 2.0 * b + c);
+            let qc = b - a;
+            let roots: Vec<f64> = if qa.abs() < 1e-12 {
+                if qb.abs() < 1e-12 {
+                    vec![]
+                } else {
+                    vec![-qc / qb]
+                }
+            } else {
+                let disc = qb * qb - 4.0 * qa * qc;
+                if disc < 0.0 {
+                    vec![]
+                } else {
+                    let s = disc.sqrt();
+                    vec![(-qb + s) / (2.0 * qa), (-qb - s) / (2.0 * qa)]
+                }
+            };
+            for t in roots.into_iter().filter(|t| *t > 0.0 && *t < 1.0) {
+                let mt = 1.0 - t;
+                let at = |k: usize| {
+                    let v = p.map(|q| if k == 0 { q.0 } else { q.1 });
+                    mt * mt * mt * v[0]
+                        + 3.0 * mt * mt * t * v[1]
+                        + 3.0 * mt * t * t * v[2]
+                        + t * t * t * v[3]
+                };
+                self.point(&Affine::IDENTITY, (at(0), at(1)));
+            }
+        }
+    }
+
+    /// An arc of an ellipse, from angle start sweeping by sweep radians, in the ellipse's own
+    /// parametrisation.
+    #[allow(clippy::too_many_arguments)]
+    fn ellipse(
+        &mut self,
+        transform: &Affine,
+        center: (f64, f64),
+        rx: f64,
+        ry: f64,
+        rotation: f64,
+        start: f64,
+        sweep: f64,
+    ) {
+        // The transformed ellipse is center + m (cos t, sin t), m maps the unit circle onto it.
+        let m = Affine {
+            e: 0.0,
+            f: 0.0,
+            ..*transform
+        } * Affine::rotate(rotation.to_degrees())
+            * Affine::scale(rx, ry);
+        let c = transform.apply(center);
+        let at = |t: f64| {
+            let (x, y) = m.apply((t.cos(), t.sin()));
+            (c.0 + x, c.1 + y)
+        };
+        self.point(&Affine::IDENTITY, at(start));
+        self.point(&Affine::IDENTITY, at(start + sweep));
+        let (lo, hi) = if sweep >= 0.0 {
+            (start, start + sweep)
+        } else {
+            (start + sweep, start)
+        };
+        for base in [m.c.atan2(m.a), m.d.atan2(m.b)] {
+            for k in 0..2 {
+                let t = base + k as f64 * PI;
+                // Bring the angle into the swept range, if it is in there at all.
+                let t = lo + (t - lo).rem_euclid(2.0 * PI);
+                if t <= hi {
+                    self.point(&Affine::IDENTITY, at(t));
+                }
+            }
+        }
+    }
+
+    /// An svg elliptical arc from the current point to end, converted to center parametrisation.
+    #[allow(clippy::too_many_arguments)]
+    fn arc(
+        &mut self,
+        transform: &Affine,
+        from: (f64, f64),
+        rx: f64,
+        ry: f64,
+        rotation: f64,
+        large_arc: bool,
+        sweep_flag: bool,
+        to: (f64, f64),
+    ) {
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if rx == 0.0 || ry == 0.0 || from == to {
+            self.point(transform, from);
+            self.point(transform, to);
+            return;
+        }
+        // https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter
+        let phi = rotation.to_radians();
+        let (sin, cos) = phi.sin_cos();
+        let dx = (from.0 - to.0) / 2.0;
+        let dy = (from.1 - to.1) / 2.0;
+        let x1 = cos * dx + sin * dy;
+        let y1 = -sin * dx + cos * dy;
+        // Radii that are too small are scaled up until the arc fits.
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coef = (num / den).max(0.0).sqrt();
+        if large_arc == sweep_flag {
+            coef = -coef;
+        }
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+        let center = (
+            cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0,
+            sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0,
+        );
+        let angle = |ux: f64, uy: f64| uy.atan2(ux);
+        let start = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let end = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+        let mut sweep = end - start;
+        if sweep_flag && sweep < 0.0 {
+            sweep += 2.0 * PI;
+        } else if !sweep_flag && sweep > 0.0 {
+            sweep -= 2.0 * PI;
+        }
+        self.ellipse(transform, center, rx, ry, phi, start, sweep);
+    }
+
+    fn path(&mut self, data: &Data, transform: &Affine) {
+        let mut current = (0.0, 0.0);
+        let mut subpath_start = (0.0, 0.0);
+        // The last control point of the previous command if it was a cubic or a quadratic curve,
+        // smooth curves only reflect the control point of a curve of their own kind.
+        let mut last_cubic: Option<(f64, f64)> = None;
+        let mut last_quadratic: Option<(f64, f64)> = None;
+        for command in data.iter() {
+            let absolute = |position: &Position, current: (f64, f64), x: f64, y: f64| match position
+            {
+                Position::Absolute => (x, y),
+                Position::Relative => (current.0 + x, current.1 + y),
+            };
+            let reflect = |control: Option<(f64, f64)>, current: (f64, f64)| {
+                control.map_or(current, |c| (2.0 * current.0 - c.0, 2.0 * current.1 - c.1))
+            };
+            let (mut cubic, mut quadratic) = (None, None);
+            match command {
+                Command::Move(position, p) => {
+                    for (i, q) in p.chunks_exact(2).enumerate() {
+                        current = absolute(position, current, q[0] as f64, q[1] as f64);
+                        if i == 0 {
+                            subpath_start = current;
+                        }
+                        self.point(transform, current);
+                    }
+                }
+                Command::Line(position, p) => {
+                    for q in p.chunks_exact(2) {
+                        current = absolute(position, current, q[0] as f64, q[1] as f64);
+                        self.point(transform, current);
+                    }
+                }
+                Command::HorizontalLine(position, p) => {
+                    for q in p.iter() {
+                        current.0 = match position {
+                            Position::Absolute => *q as f64,
+                            Position::Relative => current.0 + *q as f64,
+                        };
+                        self.point(transform, current);
+                    }
+                }
+                Command::VerticalLine(position, p) => {
+                    for q in p.iter() {
+                        current.1 = match position {
+                            Position::Absolute => *q as f64,
+                            Position::Relative => current.1 + *q as f64,
+                        };
+                        self.point(transform, current);
+                    }
+                }
+                Command::CubicCurve(position, p) => {
+                    for q in p.chunks_exact(6) {
+                        let c1 = absolute(position, current, q[0] as f64, q[1] as f64);
+                        let c2 = absolute(position, current, q[2] as f64, q[3] as f64);
+                        let end = absolute(position, current, q[4] as f64, q[5] as f64);
+                        self.cubic(transform, [current, c1, c2, end]);
+                        cubic = Some(c2);
+                        current = end;
+                    }
+                }
+                Command::SmoothCubicCurve(position, p) => {
+                    for q in p.chunks_exact(4) {
+                        let c1 = reflect(cubic.or(last_cubic), current);
+                        let c2 = absolute(position, current, q[0] as f64, q[1] as f64);
+                        let end = absolute(position, current, q[2] as f64, q[3] as f64);
+                        self.cubic(transform, [current, c1, c2, end]);
+                        cubic = Some(c2);
+                        current = end;
+                    }
+                }
+                Command::QuadraticCurve(position, p) => {
+                    for q in p.chunks_exact(4) {
+                        let c = absolute(position, current, q[0] as f64, q[1] as f64);
+                        let end = absolute(position, current, q[2] as f64, q[3] as f64);
+                        self.quadratic(transform, current, c, end);
+                        quadratic = Some(c);
+                        current = end;
+                    }
+                }
+                Command::SmoothQuadraticCurve(position, p) => {
+                    for q in p.chunks_exact(2) {
+                        let c = reflect(quadratic.or(last_quadratic), current);
+                        let end = absolute(position, current, q[0] as f64, q[1] as f64);
+                        self.quadratic(transform, current, c, end);
+                        quadratic = Some(c);
+                        current = end;
+                    }
+                }
+                Command::EllipticalArc(position, p) => {
+                    for q in p.chunks_exact(7) {
+                        let end = absolute(position, current, q[5] as f64, q[6] as f64);
+                        self.arc(
+                            transform,
+                            current,
+                            q[0] as f64,
+                            q[1] as f64,
+                            q[2] as f64,
+                            q[3] != 0.0,
+                            q[4] != 0.0,
+                            end,
+                        );
+                        current = end;
+                    }
+                }
+                Command::Close => {
+                    current = subpath_start;
+                }
+            }
+            last_cubic = cubic;
+            last_quadratic = quadratic;
+        }
+    }
+
+    fn quadratic(&mut self, transform: &Affine, p0: (f64, f64), c: (f64, f64), p1: (f64, f64)) {
+        // A quadratic curve is a cubic with its control points two thirds towards the control.
+        let lerp = |a: (f64, f64), b: (f64, f64)| {
+            (a.0 + (b.0 - a.0) * 2.0 / 3.0, a.1 + (b.1 - a.1) * 2.0 / 3.0)
+        };
+        self.cubic(transform, [p0, lerp(p0, c), lerp(p1, c), p1]);
+    }
+}
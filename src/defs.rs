@@ -0,0 +1,520 @@
+use crate::color::Color;
+use svg::node::element::{
+    Circle, Definitions, Element, Line, LinearGradient, Pattern as PatternElement, RadialGradient,
+    Rectangle, Stop,
+};
+use svg::node::{Node, Value};
+
+/// Collects definitions like gradients and patterns, to be written in a single defs element.
+///
+/// Identical definitions are only stored once and share their id. Ids are derived from the
+/// definition itself, so a definition gets the same id in every registry with the same prefix, and
+/// different definitions get different ids.
+#[derive(Debug, Clone)]
+pub struct Defs {
+    prefix: String,
+    // The serialized node without its id, the id, and the node with the id.
+    entries: Vec<(String, String, Box<dyn Node>)>,
+}
+
+impl Default for Defs {
+    fn default() -> Self {
+        Defs::with_prefix("def")
+    }
+}
+
+/// Errors from adding a definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefsError {
+    /// The node has no attributes to hold an id, like a text node.
+    Bare,
+    /// A different definition was already added with this id.
+    DuplicateId(String),
+}
+
+impl std::fmt::Display for DefsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefsError::Bare => write!(f, "node can't have an id"),
+            DefsError::DuplicateId(id) => write!(f, "id {:?} is already defined", id),
+        }
+    }
+}
+
+impl std::error::Error for DefsError {}
+
+impl Defs {
+    /// Create a registry whose ids start with "def".
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a registry whose ids start with the prefix.
+    pub fn with_prefix<T: Into<String>>(prefix: T) -> Self {
+        Defs {
+            prefix: prefix.into(),
+            entries: vec![],
+        }
+    }
+
+    /// Add a definition and return its id, a node that is already present returns the existing id.
+    ///
+    /// A node that has an id keeps it, otherwise it is assigned one derived from its content.
+    pub fn add<T: Into<Box<dyn Node>>>(&mut self, node: T) -> Result<String, DefsError> {
+        let mut node = node.into();
+        let key = node.to_string();
+        if let Some(id) = self.find(&key) {
+            return Ok(id);
+        }
+        let Some(attributes) = node.get_attributes_mut() else {
+            return Err(DefsError::Bare);
+        };
+        let id = match attributes.get("id").map(|id| id.to_string()) {
+            Some(id) if self.contains(&id) => return Err(DefsError::DuplicateId(id)),
+            Some(id) => id,
+            None => {
+                let id = self.derive_id(&key);
+                attributes.insert("id".to_owned(), id.clone().into());
+                id
+            }
+        };
+        self.entries.push((key, id.clone(), node));
+        Ok(id)
+    }
+
+    /// Add an element without an id, like a gradient or pattern, and return its id.
+    fn insert(&mut self, mut element: Element) -> String {
+        let key = element.to_string();
+        if let Some(id) = self.find(&key) {
+            return id;
+        }
+        let id = self.derive_id(&key);
+        element.assign("id", id.clone());
+        self.entries.push((key, id.clone(), Box::new(element)));
+        id
+    }
+
+    /// The id of the definition serialized as key, if it was added.
+    fn find(&self, key: &str) -> Option<String> {
+        self.entries
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, id, _)| id.clone())
+    }
+
+    /// An id for the definition serialized as key, from a hash of the key.
+    fn derive_id(&self, key: &str) -> String {
+        // FNV-1a, which unlike the hashers in std is stable across releases.
+        let hash = key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        let id = format!("{}-{:016x}", self.prefix, hash);
+        // Only an explicit id can already be taken, a number tells them apart.
+        let mut candidate = id.clone();
+        let mut n = 1;
+        while self.contains(&candidate) {
+            candidate = format!("{id}-{n}");
+            n += 1;
+        }
+        candidate
+    }
+
+    /// Whether a definition with this id was added.
+    fn contains(&self, id: &str) -> bool {
+        self.entries.iter().any(|(_, i, _)| i == id)
+    }
+
+    /// A reference to the definition with the provided id, for use in fill and stroke.
+    pub fn url(id: &str) -> String {
+        format!("url(#{id})")
+    }
+
+    /// The number of definitions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no definitions.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Render the definitions to a defs element.
+    pub fn svg(&self) -> Definitions {
+        let mut defs = Definitions::new();
+        for (_, _, node) in self.entries.iter() {
+            defs.append(node.clone());
+        }
+        defs
+    }
+}
+
+impl From<Defs> for Box<dyn Node + 'static> {
+    fn from(val: Defs) -> Self {
+        Box::new(val.svg())
+    }
+}
+
+/// The geometry of a gradient.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GradientShape {
+    /// Colors change along the line from the start to the end point.
+    Linear { x1: f64, y1: f64, x2: f64, y2: f64 },
+    /// Colors change from the focal point outwards to the circle.
+    Radial {
+        cx: f64,
+        cy: f64,
+        r: f64,
+        fx: f64,
+        fy: f64,
+    },
+}
+
+/// A linear or radial gradient, through a number of color stops.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    shape: GradientShape,
+    stops: Vec<(f64, Color)>,
+    user_space: bool,
+}
+
+impl Gradient {
+    /// A linear gradient across the bounding box of the element it is applied to, at an angle
+    /// in degrees, clockwise from left to right.
+    pub fn linear(angle: f64) -> Self {
+        let (dx, dy) = (
+            angle.to_radians().cos() / 2.0,
+            angle.to_radians().sin() / 2.0,
+        );
+        Gradient::linear_points(0.5 - dx, 0.5 - dy, 0.5 + dx, 0.5 + dy)
+    }
+
+    /// A linear gradient between two points.
+    pub fn linear_points(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Gradient {
+            shape: GradientShape::Linear { x1, y1, x2, y2 },
+            stops: vec![],
+            user_space: false,
+        }
+    }
+
+    /// A radial gradient from the center of the bounding box of the element it is applied to.
+    pub fn radial() -> Self {
+        Gradient::radial_at(0.5, 0.5, 0.5)
+    }
+
+    /// A radial gradient from the center to the circle with the provided radius.
+    pub fn radial_at(cx: f64, cy: f64, r: f64) -> Self {
+        Gradient {
+            shape: GradientShape::Radial {
+                cx,
+                cy,
+                r,
+                fx: cx,
+                fy: cy,
+            },
+            stops: vec![],
+            user_space: false,
+        }
+    }
+
+    /// Returns a gradient with a stop added, at an offset from 0 to 1 along the gradient.
+    pub fn stop(mut self, offset: f64, color: Color) -> Self {
+        self.stops.push((offset.clamp(0.0, 1.0), color));
+        self
+    }
+
+    /// Returns a gradient with the colors added as equally spaced stops.
+    pub fn stops(mut self, colors: &[Color]) -> Self {
+        let n = colors.len().saturating_sub(1).max(1) as f64;
+        for (i, color) in colors.iter().enumerate() {
+            self = self.stop(i as f64 / n, *color);
+        }
+        self
+    }
+
+    /// Returns a radial gradient with the focal point moved, a linear gradient is unchanged.
+    pub fn focus(mut self, x: f64, y: f64) -> Self {
+        if let GradientShape::Radial { fx, fy, .. } = &mut self.shape {
+            *fx = x;
+            *fy = y;
+        }
+        self
+    }
+
+    /// Returns a gradient whose coordinates are in the user space instead of relative to the
+    /// bounding box, such that it spans multiple elements, like all segments of a pie chart.
+    pub fn user_space(mut self) -> Self {
+        self.user_space = true;
+        self
+    }
+
+    /// The shape of this gradient.
+    pub fn shape(&self) -> GradientShape {
+        self.shape
+    }
+
+    /// Render the gradient to a linearGradient or radialGradient element, without an id.
+    pub fn svg(&self) -> Box<dyn Node> {
+        Box::new(self.element())
+    }
+
+    fn element(&self) -> Element {
+        let units = if self.user_space {
+            "userSpaceOnUse"
+        } else {
+            "objectBoundingBox"
+        };
+        match self.shape {
+            GradientShape::Linear { x1, y1, x2, y2 } => self
+                .add_stops(LinearGradient::new())
+                .set("x1", x1)
+                .set("y1", y1)
+                .set("x2", x2)
+                .set("y2", y2)
+                .set("gradientUnits", units)
+                .into(),
+            GradientShape::Radial { cx, cy, r, fx, fy } => self
+                .add_stops(RadialGradient::new())
+                .set("cx", cx)
+                .set("cy", cy)
+                .set("r", r)
+                .set("fx", fx)
+                .set("fy", fy)
+                .set("gradientUnits", units)
+                .into(),
+        }
+    }
+
+    fn add_stops<T: Node>(&self, mut node: T) -> T {
+        for (offset, color) in self.stops.iter() {
+            let mut stop = Stop::new()
+                .set("offset", *offset)
+                .set("stop-color", color.with_alpha(1.0));
+            if color.a < 1.0 {
+                stop = stop.set("stop-opacity", color.a);
+            }
+            node.append(stop);
+        }
+        node
+    }
+}
+
+/// The marks a pattern repeats.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PatternStyle {
+    /// Parallel lines.
+    Lines,
+    /// Two sets of parallel lines, perpendicular to each other.
+    CrossHatch,
+    /// A grid of dots.
+    Dots,
+}
+
+/// A repeating pattern of lines or dots, to tell shapes apart without relying on color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    style: PatternStyle,
+    spacing: f64,
+    angle: f64,
+    stroke: Color,
+    stroke_width: f64,
+    background: Option<Color>,
+}
+
+impl Pattern {
+    fn new(style: PatternStyle, angle: f64) -> Self {
+        Pattern {
+            style,
+            spacing: 6.0,
+            angle,
+            stroke: Color::BLACK,
+            stroke_width: 1.0,
+            background: None,
+        }
+    }
+
+    /// Diagonal lines, rising to the right.
+    pub fn diagonal() -> Self {
+        Pattern::new(PatternStyle::Lines, -45.0)
+    }
+
+    /// Diagonal lines in both directions.
+    pub fn cross_hatch() -> Self {
+        Pattern::new(PatternStyle::CrossHatch, 45.0)
+    }
+
+    /// Dots on a square grid.
+    pub fn dots() -> Self {
+        Pattern::new(PatternStyle::Dots, 0.0)
+    }
+
+    /// Horizontal lines.
+    pub fn horizontal() -> Self {
+        Pattern::new(PatternStyle::Lines, 0.0)
+    }
+
+    /// Vertical lines.
+    pub fn vertical() -> Self {
+        Pattern::new(PatternStyle::Lines, 90.0)
+    }
+
+    /// Returns a pattern with the distance between lines or dots set to the provided value.
+    pub fn spacing(mut self, spacing: f64) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Returns a pattern rotated by the angle in degrees, replacing the angle of its style.
+    pub fn angle(mut self, angle: f64) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    /// Returns a pattern with the color of its lines or dots set to the provided value.
+    pub fn stroke(mut self, color: Color) -> Self {
+        self.stroke = color;
+        self
+    }
+
+    /// Returns a pattern with the line width, or the dot radius, set to the provided value.
+    pub fn stroke_width(mut self, width: f64) -> Self {
+        self.stroke_width = width;
+        self
+    }
+
+    /// Returns a pattern drawn on top of a solid background, instead of a transparent one.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Render the pattern to a pattern element, without an id.
+    pub fn svg(&self) -> PatternElement {
+        let s = self.spacing;
+        let mut pattern = PatternElement::new()
+            .set("patternUnits", "userSpaceOnUse")
+            .set("width", s)
+            .set("height", s)
+            .set("patternTransform", format!("rotate({})", self.angle));
+        if let Some(background) = self.background {
+            pattern = pattern.add(
+                Rectangle::new()
+                    .set("width", s)
+                    .set("height", s)
+                    .set("fill", background),
+            );
+        }
+        let line = |x1: f64, y1: f64, x2: f64, y2: f64| {
+            Line::new()
+                .set("x1", x1)
+                .set("y1", y1)
+                .set("x2", x2)
+                .set("y2", y2)
+                .set("stroke", self.stroke)
+                .set("stroke-width", self.stroke_width)
+        };
+        // Marks are centered in the tile, such that strokes don't get cut off at its edges.
+        match self.style {
+            PatternStyle::Lines => pattern.add(line(0.0, s / 2.0, s, s / 2.0)),
+            PatternStyle::CrossHatch => {
+                pattern
+                    .add(line(0.0, s / 2.0, s, s / 2.0))
+                    .add(line(s / 2.0, 0.0, s / 2.0, s))
+            }
+            PatternStyle::Dots => pattern.add(
+                Circle::new()
+                    .set("cx", s / 2.0)
+                    .set("cy", s / 2.0)
+                    .set("r", self.stroke_width)
+                    .set("fill", self.stroke),
+            ),
+        }
+    }
+}
+
+/// What to fill or stroke a shape with.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Paint {
+    /// Nothing, the shape is not filled or stroked.
+    #[default]
+    None,
+    /// A plain color.
+    Color(Color),
+    /// A gradient, which is added to the definitions when used.
+    Gradient(Gradient),
+    /// A pattern, which is added to the definitions when used.
+    Pattern(Pattern),
+    /// A reference to a definition by its id.
+    Url(String),
+}
+
+impl Paint {
+    /// The attribute value for this paint, adding any definitions it needs.
+    pub fn resolve(&self, defs: &mut Defs) -> Value {
+        match self {
+            Paint::None => "none".into(),
+            Paint::Color(color) => (*color).into(),
+            Paint::Gradient(gradient) => Defs::url(&defs.insert(gradient.element())).into(),
+            Paint::Pattern(pattern) => Defs::url(&defs.insert(pattern.svg().into())).into(),
+            Paint::Url(id) => Defs::url(id).into(),
+        }
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(val: Color) -> Self {
+        Paint::Color(val)
+    }
+}
+
+impl From<Gradient> for Paint {
+    fn from(val: Gradient) -> Self {
+        Paint::Gradient(val)
+    }
+}
+
+impl From<Pattern> for Paint {
+    fn from(val: Pattern) -> Self {
+        Paint::Pattern(val)
+    }
+}
+
+pub trait Painted: svg::Node {
+    /// Fill this element with the paint, definitions it needs are added to defs.
+    fn fill_paint(&mut self, paint: &Paint, defs: &mut Defs)
+    where
+        Self: Sized,
+    {
+        self.assign("fill", paint.resolve(defs));
+    }
+    /// Fill this element with the paint, definitions it needs are added to defs.
+    fn filled(self, paint: &Paint, defs: &mut Defs) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.fill_paint(paint, defs);
+        z
+    }
+
+    /// Stroke this element with the paint, definitions it needs are added to defs.
+    fn stroke_paint(&mut self, paint: &Paint, defs: &mut Defs)
+    where
+        Self: Sized,
+    {
+        self.assign("stroke", paint.resolve(defs));
+    }
+    /// Stroke this element with the paint, definitions it needs are added to defs.
+    fn stroked(self, paint: &Paint, defs: &mut Defs) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.stroke_paint(paint, defs);
+        z
+    }
+}
+
+// Blanket implementation for all nodes, any element can be filled or stroked.
+impl<T: svg::node::Node> Painted for T {}
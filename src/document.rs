@@ -0,0 +1,193 @@
+use crate::bbox::{bounds, Rect};
+use crate::defs::{Defs, Paint};
+use crate::transform::format_number;
+use svg::node::element::Rectangle;
+use svg::node::Node;
+use svg::Document;
+
+/// The unit of the physical size of a document.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Unit {
+    /// Pixels, as is.
+    #[default]
+    Px,
+    /// Millimeters, converted from pixels at the dpi.
+    Mm,
+    /// Inches, converted from pixels at the dpi.
+    In,
+}
+
+impl Unit {
+    fn suffix(&self) -> &'static str {
+        match self {
+            Unit::Px => "px",
+            Unit::Mm => "mm",
+            Unit::In => "in",
+        }
+    }
+
+    /// The number of this unit in a pixel at the provided dpi.
+    fn per_pixel(&self, dpi: f64) -> f64 {
+        match self {
+            Unit::Px => 1.0,
+            Unit::Mm => 25.4 / dpi,
+            Unit::In => 1.0 / dpi,
+        }
+    }
+}
+
+/// A document whose viewBox fits the nodes it contains.
+///
+/// The viewBox is the bounding box of all nodes, grown by the margin. Its physical size is the
+/// size of the viewBox times the scale in pixels, expressed in the unit at the dpi, or a fixed
+/// width. Of [`Canvas::scale`] and [`Canvas::width`] the last one called decides the size.
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    nodes: Vec<Box<dyn Node>>,
+    margin: f64,
+    scale: f64,
+    width: Option<f64>,
+    unit: Unit,
+    dpi: f64,
+    background: Option<Paint>,
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Canvas {
+            nodes: vec![],
+            margin: 0.0,
+            scale: 1.0,
+            width: None,
+            unit: Unit::Px,
+            dpi: 96.0,
+            background: None,
+        }
+    }
+}
+
+impl Canvas {
+    /// Create an empty canvas, without margin and with one pixel per user unit.
+    pub fn new() -> Self {
+        Canvas::default()
+    }
+
+    /// Returns the canvas with the node added on top of the previous nodes.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T: Into<Box<dyn Node>>>(mut self, node: T) -> Self {
+        self.nodes.push(node.into());
+        self
+    }
+
+    /// Returns the canvas with the margin around the nodes set, in user units.
+    pub fn margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Returns the canvas with the number of pixels per user unit set, replacing a fixed width.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self.width = None;
+        self
+    }
+
+    /// Returns the canvas with a fixed physical width in its unit, the height follows the aspect ratio.
+    ///
+    /// The width replaces the scale, until the scale is set again.
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Returns the canvas with the unit of the physical size set.
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Returns the canvas with the dots per inch set, used to convert pixels to mm or inches.
+    pub fn dpi(mut self, dpi: f64) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Returns the canvas with a background covering the viewBox.
+    pub fn background<T: Into<Paint>>(mut self, paint: T) -> Self {
+        self.background = Some(paint.into());
+        self
+    }
+
+    /// The viewBox, the bounds of all nodes and the margin.
+    ///
+    /// Without anything to draw this is just the margin around the origin, or a unit square at the
+    /// origin if there is no margin, since a viewBox without area disables rendering.
+    pub fn view_box(&self) -> Rect {
+        let content = self
+            .nodes
+            .iter()
+            .filter_map(|n| bounds(n.as_ref()))
+            .reduce(|a, b| a.union(&b));
+        match content {
+            Some(rect) => rect.expand(self.margin),
+            None if self.margin > 0.0 => Rect::new((0.0, 0.0), (0.0, 0.0)).expand(self.margin),
+            None => Rect::new((0.0, 0.0), (1.0, 1.0)),
+        }
+    }
+
+    /// The physical width and height, in the unit of the canvas.
+    pub fn size(&self) -> (f64, f64) {
+        let view = self.view_box();
+        match self.width {
+            Some(width) if view.width() > 0.0 => (width, width * view.height() / view.width()),
+            _ => {
+                let factor = self.scale * self.unit.per_pixel(self.dpi);
+                (view.width() * factor, view.height() * factor)
+            }
+        }
+    }
+
+    /// Render the canvas to a document, with a background first if one is set and the nodes on
+    /// top of it in the order they were added.
+    pub fn svg(&self) -> Document {
+        let view = self.view_box();
+        let (width, height) = self.size();
+        let suffix = self.unit.suffix();
+        let mut document = Document::new()
+            .set(
+                "viewBox",
+                [view.min_x, view.min_y, view.width(), view.height()]
+                    .map(format_number)
+                    .join(" "),
+            )
+            .set("width", format!("{}{}", format_number(width), suffix))
+            .set("height", format!("{}{}", format_number(height), suffix));
+
+        if let Some(background) = &self.background {
+            // A prefix of its own, so ids cannot collide with definitions among the nodes.
+            let mut defs = Defs::with_prefix("background");
+            let fill = background.resolve(&mut defs);
+            if !defs.is_empty() {
+                document = document.add(defs);
+            }
+            document = document.add(
+                Rectangle::new()
+                    .set("x", view.min_x)
+                    .set("y", view.min_y)
+                    .set("width", view.width())
+                    .set("height", view.height())
+                    .set("fill", fill),
+            );
+        }
+        for node in self.nodes.iter() {
+            document = document.add(node.clone());
+        }
+        document
+    }
+}
+
+impl From<Canvas> for Document {
+    fn from(val: Canvas) -> Self {
+        val.svg()
+    }
+}
@@ -5,6 +5,7 @@ use svg::node::element::Element;
 use svg::node::{Attributes, Children, Node, NodeDefaultHash, Value};
 
 // https://github.com/bodoni/svg/blob/d58e91878b98f061b280caa0ab95bb460f2e422e/src/node/element/tag.rs#L127
+#[allow(non_upper_case_globals)]
 mod tag {
     macro_rules! implement {
         ($($const_name:ident: $tag_name:expr,)*) => ($(
@@ -28,6 +29,7 @@ macro_rules! implement_nested(
     ($struct_name:ident::$field_name:ident [$($indicator_name:ident),*]) => (
         impl $struct_name {
             /// Append a node.
+            #[allow(clippy::should_implement_trait)]
             pub fn add<T>(mut self, node: T) -> Self
             where
                 T: Into<Box<dyn Node>>,
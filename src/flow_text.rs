@@ -39,10 +39,10 @@ impl FlowText {
         }
     }
 
-    fn assemble(&self) -> Box<(dyn svg::Node + 'static)> {
+    fn assemble(&self) -> Box<dyn svg::Node + 'static> {
         let mut root = self.root.clone();
 
-        let mut region = self.region.clone();
+        let region = self.region.clone();
 
         for (k, v) in self.attributes.iter() {
             root.assign(k.clone(), v.clone());
@@ -60,12 +60,12 @@ impl FlowText {
         U: Into<Value>,
     {
         let mut z = self;
-        z.attributes.insert(name.into(), value.into());
+        z.assign(name, value);
         z
     }
+    /// Assign an attribute to the root element.
     fn assign<T, U>(&mut self, name: T, value: U)
     where
-        Self: Sized,
         T: Into<String>,
         U: Into<Value>,
     {
@@ -73,7 +73,7 @@ impl FlowText {
     }
 }
 
-impl From<FlowText> for Box<(dyn svg::Node + 'static)> {
+impl From<FlowText> for Box<dyn svg::Node + 'static> {
     fn from(val: FlowText) -> Self {
         val.assemble()
     }
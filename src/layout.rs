@@ -0,0 +1,385 @@
+use crate::bbox::{bounds, Rect};
+use crate::transform::Transformed;
+use svg::node::element::{Group, Rectangle};
+use svg::node::Node;
+
+/// Where a child is placed in the space available to it.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Align {
+    /// Left or top.
+    #[default]
+    Start,
+    Center,
+    /// Right or bottom.
+    End,
+}
+
+impl Align {
+    /// The offset of something of size inside space.
+    fn offset(&self, size: f64, space: f64) -> f64 {
+        match self {
+            Align::Start => 0.0,
+            Align::Center => (space - size) / 2.0,
+            Align::End => space - size,
+        }
+    }
+}
+
+/// The direction in which a stack places its children.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Direction {
+    /// Left to right.
+    #[default]
+    Horizontal,
+    /// Top to bottom.
+    Vertical,
+}
+
+/// A child and its bounding box, children without one take no space.
+#[derive(Debug, Clone)]
+struct Item {
+    node: Box<dyn Node>,
+    rect: Rect,
+}
+
+fn measure(children: &[Box<dyn Node>]) -> Vec<Item> {
+    children
+        .iter()
+        .map(|node| Item {
+            node: node.clone(),
+            rect: bounds(node.as_ref()).unwrap_or(Rect::new((0.0, 0.0), (0.0, 0.0))),
+        })
+        .collect()
+}
+
+impl Item {
+    /// The child moved such that its bounding box starts at x, y.
+    fn placed(&self, x: f64, y: f64) -> Group {
+        Group::new()
+            .add(self.node.clone())
+            .translated_xy(x - self.rect.min_x, y - self.rect.min_y)
+    }
+}
+
+/// The group holding the placed children, with an invisible rectangle spanning the padding.
+fn padded(children: Vec<Group>, width: f64, height: f64, padding: f64) -> Group {
+    let mut group = Group::new();
+    if padding != 0.0 {
+        // Makes the padding part of the bounding box, such that nested layouts honour it.
+        group = group.add(
+            Rectangle::new()
+                .set("width", width + 2.0 * padding)
+                .set("height", height + 2.0 * padding)
+                .set("fill", "none"),
+        );
+    }
+    for child in children {
+        group = group.add(child);
+    }
+    group
+}
+
+/// Children placed next to each other, horizontally or vertically.
+///
+/// The stack starts at the origin, the children are aligned along the other axis.
+#[derive(Debug, Clone, Default)]
+pub struct Stack {
+    direction: Direction,
+    children: Vec<Box<dyn Node>>,
+    gap: f64,
+    padding: f64,
+    align: Align,
+}
+
+impl Stack {
+    /// A stack placing its children from left to right.
+    pub fn horizontal() -> Self {
+        Stack {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        }
+    }
+
+    /// A stack placing its children from top to bottom.
+    pub fn vertical() -> Self {
+        Stack {
+            direction: Direction::Vertical,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the stack with the child added at the end.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T: Into<Box<dyn Node>>>(mut self, child: T) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// Returns the stack with the space between children set.
+    pub fn gap(mut self, gap: f64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Returns the stack with the space around all children set.
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns the stack with the alignment across the stacking direction set.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Render the stack, with the first child's bounding box at the padding from the origin.
+    pub fn svg(&self) -> Group {
+        let items = measure(&self.children);
+        let horizontal = self.direction == Direction::Horizontal;
+        // Length along the direction and breadth across it.
+        let size = |r: &Rect| {
+            if horizontal {
+                (r.width(), r.height())
+            } else {
+                (r.height(), r.width())
+            }
+        };
+        let breadth = items.iter().map(|i| size(&i.rect).1).fold(0.0, f64::max);
+        let mut position = 0.0;
+        let mut placed = vec![];
+        for item in items.iter() {
+            let (length, across) = size(&item.rect);
+            let offset = self.align.offset(across, breadth);
+            let (x, y) = if horizontal {
+                (position, offset)
+            } else {
+                (offset, position)
+            };
+            placed.push(item.placed(x + self.padding, y + self.padding));
+            position += length + self.gap;
+        }
+        let length = (position - self.gap).max(0.0);
+        if horizontal {
+            padded(placed, length, breadth, self.padding)
+        } else {
+            padded(placed, breadth, length, self.padding)
+        }
+    }
+}
+
+impl From<Stack> for Box<dyn Node + 'static> {
+    fn from(val: Stack) -> Self {
+        Box::new(val.svg())
+    }
+}
+
+/// Children placed in cells of a grid, filled row by row.
+///
+/// Columns are as wide as their widest child, rows as high as their highest child.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    columns: usize,
+    children: Vec<Box<dyn Node>>,
+    column_gap: f64,
+    row_gap: f64,
+    padding: f64,
+    align_horizontal: Align,
+    align_vertical: Align,
+}
+
+impl Grid {
+    /// A grid with the provided number of columns, rows are added as needed.
+    pub fn new(columns: usize) -> Self {
+        Grid {
+            columns: columns.max(1),
+            children: vec![],
+            column_gap: 0.0,
+            row_gap: 0.0,
+            padding: 0.0,
+            align_horizontal: Align::Start,
+            align_vertical: Align::Start,
+        }
+    }
+
+    /// Returns the grid with the child added in the next cell.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T: Into<Box<dyn Node>>>(mut self, child: T) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// Returns the grid with the space between both columns and rows set.
+    pub fn gap(mut self, gap: f64) -> Self {
+        self.column_gap = gap;
+        self.row_gap = gap;
+        self
+    }
+
+    /// Returns the grid with the space between columns and between rows set.
+    pub fn gaps(mut self, column_gap: f64, row_gap: f64) -> Self {
+        self.column_gap = column_gap;
+        self.row_gap = row_gap;
+        self
+    }
+
+    /// Returns the grid with the space around all cells set.
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns the grid with the alignment of children within their cell set.
+    pub fn align(mut self, horizontal: Align, vertical: Align) -> Self {
+        self.align_horizontal = horizontal;
+        self.align_vertical = vertical;
+        self
+    }
+
+    /// The number of rows, given the number of children.
+    pub fn rows(&self) -> usize {
+        self.children.len().div_ceil(self.columns)
+    }
+
+    /// Render the grid, with the first cell at the padding from the origin.
+    pub fn svg(&self) -> Group {
+        let items = measure(&self.children);
+        let mut widths = vec![0.0f64; self.columns];
+        let mut heights = vec![0.0f64; self.rows()];
+        for (i, item) in items.iter().enumerate() {
+            let (row, column) = (i / self.columns, i % self.columns);
+            widths[column] = widths[column].max(item.rect.width());
+            heights[row] = heights[row].max(item.rect.height());
+        }
+        // The start of each column and row, with a final entry at the end.
+        let starts = |sizes: &[f64], gap: f64| {
+            let mut starts = vec![0.0];
+            for size in sizes {
+                starts.push(starts.last().unwrap() + size + gap);
+            }
+            starts
+        };
+        let xs = starts(&widths, self.column_gap);
+        let ys = starts(&heights, self.row_gap);
+
+        let mut placed = vec![];
+        for (i, item) in items.iter().enumerate() {
+            let (row, column) = (i / self.columns, i % self.columns);
+            let x = xs[column]
+                + self
+                    .align_horizontal
+                    .offset(item.rect.width(), widths[column]);
+            let y = ys[row] + self.align_vertical.offset(item.rect.height(), heights[row]);
+            placed.push(item.placed(x + self.padding, y + self.padding));
+        }
+        let width = (xs[xs.len() - 1] - self.column_gap).max(0.0);
+        let height = (ys[ys.len() - 1] - self.row_gap).max(0.0);
+        padded(placed, width, height, self.padding)
+    }
+}
+
+impl From<Grid> for Box<dyn Node + 'static> {
+    fn from(val: Grid) -> Self {
+        Box::new(val.svg())
+    }
+}
+
+/// Children placed left to right, wrapping onto a new line when the width is exceeded.
+///
+/// Lines are as high as their highest child, children are aligned vertically within their line.
+#[derive(Debug, Clone)]
+pub struct Flow {
+    width: f64,
+    children: Vec<Box<dyn Node>>,
+    gap: f64,
+    line_gap: f64,
+    padding: f64,
+    align: Align,
+}
+
+impl Flow {
+    /// A flow wrapping at the provided width, which excludes the padding.
+    pub fn new(width: f64) -> Self {
+        Flow {
+            width,
+            children: vec![],
+            gap: 0.0,
+            line_gap: 0.0,
+            padding: 0.0,
+            align: Align::Start,
+        }
+    }
+
+    /// Returns the flow with the child added at the end.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T: Into<Box<dyn Node>>>(mut self, child: T) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// Returns the flow with the space between children and between lines set.
+    pub fn gap(mut self, gap: f64) -> Self {
+        self.gap = gap;
+        self.line_gap = gap;
+        self
+    }
+
+    /// Returns the flow with the space between lines set.
+    pub fn line_gap(mut self, line_gap: f64) -> Self {
+        self.line_gap = line_gap;
+        self
+    }
+
+    /// Returns the flow with the space around all children set.
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns the flow with the vertical alignment within a line set.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Render the flow, with the first line at the padding from the origin.
+    pub fn svg(&self) -> Group {
+        let items = measure(&self.children);
+        // Split into lines, a child wider than the flow gets a line of its own.
+        let mut lines: Vec<Vec<&Item>> = vec![];
+        let mut x = 0.0;
+        for item in items.iter() {
+            match lines.last_mut() {
+                Some(line) if x + item.rect.width() <= self.width => line.push(item),
+                _ => {
+                    lines.push(vec![item]);
+                    x = 0.0;
+                }
+            }
+            x += item.rect.width() + self.gap;
+        }
+
+        let mut placed = vec![];
+        let mut width = 0.0f64;
+        let mut y = 0.0;
+        for line in lines.iter() {
+            let height = line.iter().map(|i| i.rect.height()).fold(0.0, f64::max);
+            let mut x = 0.0;
+            for item in line {
+                let offset = self.align.offset(item.rect.height(), height);
+                placed.push(item.placed(x + self.padding, y + offset + self.padding));
+                x += item.rect.width() + self.gap;
+            }
+            width = width.max(x - self.gap);
+            y += height + self.line_gap;
+        }
+        let height = (y - self.line_gap).max(0.0);
+        padded(placed, width, height, self.padding)
+    }
+}
+
+impl From<Flow> for Box<dyn Node + 'static> {
+    fn from(val: Flow) -> Self {
+        Box::new(val.svg())
+    }
+}
@@ -0,0 +1,235 @@
+use crate::plot::Marker;
+use svg::node::element::{Group, Line, Rectangle, Text};
+use svg::node::{Attributes, Node, Value};
+
+/// The shape drawn in front of a legend entry's label.
+#[derive(Debug, Clone, Default)]
+pub enum Swatch {
+    /// A filled square, for bars and pie segments.
+    #[default]
+    Square,
+    /// A horizontal line, for line series.
+    Line,
+    /// A marker, for scatter series.
+    Marker(Marker),
+}
+
+/// A single entry in a legend, a swatch and its label.
+#[derive(Debug, Clone, Default)]
+pub struct LegendEntry {
+    label: String,
+    swatch: Swatch,
+    attributes: Attributes,
+}
+
+impl LegendEntry {
+    /// Create an entry with the provided label and a square swatch.
+    pub fn new<T: Into<String>>(label: T) -> Self {
+        LegendEntry {
+            label: label.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns an entry with the swatch shape set to the provided value.
+    pub fn swatch(mut self, swatch: Swatch) -> Self {
+        self.swatch = swatch;
+        self
+    }
+
+    /// Set an attribute on the swatch, like its fill or stroke.
+    pub fn set<T, U>(mut self, name: T, value: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<Value>,
+    {
+        self.attributes.insert(name.into(), value.into());
+        self
+    }
+}
+
+/// The direction in which legend entries follow each other.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum LegendOrientation {
+    /// Entries are placed below each other, wrapping into columns.
+    #[default]
+    Vertical,
+    /// Entries are placed next to each other, wrapping into rows.
+    Horizontal,
+}
+
+/// A legend, lists swatches with labels.
+#[derive(Debug, Clone)]
+pub struct Legend {
+    entries: Vec<LegendEntry>,
+    orientation: LegendOrientation,
+    wrap: Option<usize>,
+    swatch_size: f64,
+    spacing: f64,
+    font_size: f64,
+    entry_width: Option<f64>,
+    label_attributes: Attributes,
+}
+
+impl Default for Legend {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            orientation: LegendOrientation::Vertical,
+            wrap: None,
+            swatch_size: 10.0,
+            spacing: 4.0,
+            font_size: 10.0,
+            entry_width: None,
+            label_attributes: Default::default(),
+        }
+    }
+}
+
+impl Legend {
+    /// Create a new, empty legend.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns a legend with the entry appended.
+    pub fn entry(mut self, entry: LegendEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Returns a legend with the orientation set to the provided value.
+    pub fn orientation(mut self, orientation: LegendOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Returns a legend that wraps after this many entries, into a new column for vertical
+    /// legends and into a new row for horizontal legends.
+    pub fn wrap(mut self, entries: usize) -> Self {
+        self.wrap = Some(entries.max(1));
+        self
+    }
+
+    /// Returns a legend with the swatch size and the spacing between entries set.
+    pub fn sized(mut self, swatch_size: f64, spacing: f64) -> Self {
+        self.swatch_size = swatch_size;
+        self.spacing = spacing;
+        self
+    }
+
+    /// Returns a legend with the label font size set to the provided value.
+    pub fn font_size(mut self, font_size: f64) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Returns a legend with a fixed entry width, instead of one estimated from the labels.
+    pub fn entry_width(mut self, width: f64) -> Self {
+        self.entry_width = Some(width);
+        self
+    }
+
+    /// Set an attribute on the labels.
+    pub fn set_label<T, U>(mut self, name: T, value: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<Value>,
+    {
+        self.label_attributes.insert(name.into(), value.into());
+        self
+    }
+
+    /// The entries in this legend.
+    pub fn entries(&self) -> &[LegendEntry] {
+        &self.entries
+    }
+
+    /// Width of an entry, the text width is estimated from the number of characters.
+    fn width_of(&self, entry: &LegendEntry) -> f64 {
+        self.entry_width.unwrap_or_else(|| {
+            let text = entry.label.chars().count() as f64 * self.font_size * 0.6;
+            self.swatch_size + self.spacing + text
+        })
+    }
+
+    fn swatch_svg(&self, entry: &LegendEntry, x: f64, y: f64) -> Box<dyn Node> {
+        let s = self.swatch_size;
+        let mut node: Box<dyn Node> = match &entry.swatch {
+            Swatch::Square => Box::new(
+                Rectangle::new()
+                    .set("x", x)
+                    .set("y", y)
+                    .set("width", s)
+                    .set("height", s),
+            ),
+            Swatch::Line => Box::new(
+                Line::new()
+                    .set("x1", x)
+                    .set("y1", y + s / 2.0)
+                    .set("x2", x + s)
+                    .set("y2", y + s / 2.0),
+            ),
+            Swatch::Marker(marker) => marker.svg(x + s / 2.0, y + s / 2.0, s * 0.8),
+        };
+        if let Some(attr) = node.get_attributes_mut() {
+            for (k, v) in entry.attributes.iter() {
+                attr.insert(k.clone(), v.clone());
+            }
+        }
+        node
+    }
+
+    /// Render the legend to svg, the top left corner is at the origin.
+    pub fn svg(&self) -> Group {
+        let mut group = Group::new();
+        let line_height = self.swatch_size.max(self.font_size) + self.spacing;
+        let wrap = self.wrap.unwrap_or(self.entries.len()).max(1);
+
+        // Entries are chunked in columns (vertical) or rows (horizontal).
+        let mut offset = 0.0;
+        for (chunk_index, chunk) in self.entries.chunks(wrap).enumerate() {
+            match self.orientation {
+                LegendOrientation::Vertical => {
+                    for (i, entry) in chunk.iter().enumerate() {
+                        let y = i as f64 * line_height;
+                        group.append(self.entry_svg(entry, offset, y));
+                    }
+                    let column_width = chunk.iter().map(|e| self.width_of(e)).fold(0.0, f64::max);
+                    offset += column_width + self.spacing * 2.0;
+                }
+                LegendOrientation::Horizontal => {
+                    let y = chunk_index as f64 * line_height;
+                    let mut x = 0.0;
+                    for entry in chunk.iter() {
+                        group.append(self.entry_svg(entry, x, y));
+                        x += self.width_of(entry) + self.spacing * 2.0;
+                    }
+                }
+            }
+        }
+        group
+    }
+
+    fn entry_svg(&self, entry: &LegendEntry, x: f64, y: f64) -> Group {
+        let mut group = Group::new();
+        group.append(self.swatch_svg(entry, x, y));
+        let mut text = Text::new(entry.label.clone())
+            .set("x", x + self.swatch_size + self.spacing)
+            .set("y", y + self.swatch_size / 2.0)
+            .set("font-size", self.font_size)
+            .set("dominant-baseline", "middle");
+        let attr = text.get_attributes_mut().unwrap();
+        for (k, v) in self.label_attributes.iter() {
+            attr.insert(k.clone(), v.clone());
+        }
+        group.append(text);
+        group
+    }
+}
+
+impl From<Legend> for Box<dyn Node + 'static> {
+    fn from(val: Legend) -> Self {
+        Box::new(val.svg())
+    }
+}
@@ -1,5 +1,12 @@
+pub mod bbox;
+pub mod color;
+pub mod defs;
+pub mod document;
 pub mod extensions;
 pub mod flow_text;
+pub mod layout;
+pub mod legend;
+pub mod palette;
 pub mod pie_chart;
 pub mod plot;
 pub mod tab;
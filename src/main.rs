@@ -3,7 +3,7 @@ use svg::node::element::Group;
 use svg::Document;
 use svg::Node;
 use svg_util::pie_chart::{PieChart, StartStyle};
-use svg_util::tab::{Tab, TabEdge};
+use svg_util::tab::{BodyCorner, Corner, Tab, TabEdge};
 use svg_util::transform::Transformed;
 
 fn piechart_canvas() -> Document {
@@ -39,8 +39,14 @@ fn make_piechart() {
 
     pie_chart.set_segments(&[0.15, 0.3, 0.4, 0.15]);
 
+    let legend = pie_chart
+        .legend(&["Rent", "Food", "Travel", "Other"])
+        .set_label("fill", "white")
+        .svg()
+        .translated_xy(120.0, -100.0);
+
     let document = piechart_canvas();
-    let document = document.add(pie_chart);
+    let document = document.add(pie_chart).add(legend);
 
     svg::save("/tmp/test_pie_chart.svg", &document).expect("failed to write svg");
 }
@@ -62,6 +68,221 @@ fn make_piechart_align_largest() {
     svg::save("/tmp/test_pie_chart_align_largest.svg", &document).expect("failed to write svg");
 }
 
+fn make_donut() {
+    use svg::node::element::Text;
+
+    let mut pie_chart = PieChart::new();
+    pie_chart.set_radius(100.0);
+    pie_chart.set_inner_radius(60.0);
+    pie_chart.set_start(-PI / 2.0, StartStyle::Edge);
+    pie_chart.set_segments(&[0.1, 0.2, 0.3, 0.15]);
+    pie_chart.set_center_caption(
+        Text::new("75%")
+            .set("fill", "white")
+            .set("font-size", 30)
+            .set("text-anchor", "middle")
+            .set("dominant-baseline", "middle"),
+    );
+
+    let document = piechart_canvas().add(pie_chart);
+    svg::save("/tmp/test_donut.svg", &document).expect("failed to write svg");
+}
+
+fn make_piechart_labels() {
+    use svg_util::pie_chart::{LabelFormat, OtherBucket};
+
+    let mut pie_chart = PieChart::from_values(&[
+        ("Compute", 5000.0),
+        ("Storage", 3000.0),
+        ("Network", 1000.0),
+        ("Support", 400.0),
+        ("Licenses", 300.0),
+        ("Misc", 300.0),
+    ])
+    .expect("values should be valid");
+    pie_chart.set_radius(100.0);
+    pie_chart.set_start(-PI / 2.0, StartStyle::Edge);
+    pie_chart.set_label_format(LabelFormat::LabelPercentage(0));
+    // Highlight the biggest cost.
+    if let Some(s) = pie_chart.segment_mut(0) {
+        s.explode = Some(10.0);
+    }
+    pie_chart.set_label_attribute("fill", "white");
+    // Everything below 5% ends up in a single segment.
+    pie_chart.set_other(OtherBucket::threshold(0.05));
+
+    let document = piechart_canvas().add(pie_chart);
+    svg::save("/tmp/test_pie_chart_labels.svg", &document).expect("failed to write svg");
+}
+
+fn make_gauge() {
+    use svg_util::color::Color;
+    use svg_util::pie_chart::Needle;
+
+    let mut pie_chart = PieChart::new();
+    pie_chart.set_radius(150.0);
+    pie_chart.set_inner_radius(100.0);
+    // A speedometer, from 7.5 to 4.5 o clock.
+    pie_chart.set_start(0.75 * PI, StartStyle::Edge);
+    pie_chart.set_end(0.25 * PI);
+    pie_chart.set_segments(&[0.6, 0.25, 0.15]);
+    for (i, color) in ["green", "yellow", "red"].iter().enumerate() {
+        if let Some(s) = pie_chart.segment_mut(i) {
+            s.color = Color::parse(color).ok();
+        }
+    }
+    pie_chart.set_needle(Needle::new(0.7).length(140.0).set("fill", "lightgray"));
+
+    let document = piechart_canvas().add(pie_chart);
+    svg::save("/tmp/test_gauge.svg", &document).expect("failed to write svg");
+}
+
+fn make_sunburst() {
+    use svg_util::palette::Palette;
+    use svg_util::pie_chart::{Sunburst, SunburstNode};
+
+    let mut sunburst = Sunburst::new();
+    sunburst.set_radius(150.0);
+    sunburst.set_inner_radius(30.0);
+    sunburst.set_start(-PI / 2.0, StartStyle::Edge);
+    sunburst.set_attribute("stroke", "black");
+    sunburst.set_palette(Palette::tol_bright());
+    // Build time per crate, module and function, in seconds.
+    sunburst.add_root(
+        SunburstNode::new("svg_util", 12.0)
+            .child(
+                SunburstNode::new("plot", 7.0)
+                    .child(SunburstNode::new("Axis::svg", 3.0))
+                    .child(SunburstNode::new("Plot::svg", 2.5)),
+            )
+            .child(SunburstNode::new("pie_chart", 4.0).child(SunburstNode::new("svg", 1.0))),
+    );
+    sunburst.add_root(
+        SunburstNode::new("svg", 6.0)
+            .child(SunburstNode::new("parser", 4.0))
+            .child(SunburstNode::new("node", 2.0)),
+    );
+    sunburst.add_root(SunburstNode::new("std", 3.0));
+
+    let document = piechart_canvas()
+        .add(sunburst.clone())
+        .add(sunburst.legend().svg().translated_xy(160.0, -190.0));
+    svg::save("/tmp/test_sunburst.svg", &document).expect("failed to write svg");
+}
+
+fn make_gradients() {
+    use svg_util::color::Color;
+    use svg_util::defs::{Defs, Gradient, Painted};
+    use svg_util::palette::Palette;
+
+    let mut defs = Defs::new();
+
+    let mut pie_chart = PieChart::new();
+    pie_chart.set_radius(100.0);
+    pie_chart.set_inner_radius(40.0);
+    pie_chart.set_segments(&[0.2, 0.3, 0.5]);
+    // Each segment fades outward, the gradient is centered on the chart instead of the segment.
+    for (i, color) in Palette::default().colors(3).into_iter().enumerate() {
+        if let Some(s) = pie_chart.segment_mut(i) {
+            s.fill = Some(
+                Gradient::radial_at(0.0, 0.0, 100.0)
+                    .user_space()
+                    .stop(0.4, color)
+                    .stop(1.0, color.darken(0.25))
+                    .into(),
+            );
+        }
+    }
+    let chart = pie_chart.svg_with_defs(&mut defs).translated_xy(-80.0, 0.0);
+
+    // Both tabs share a single gradient definition.
+    let fade = Gradient::linear(90.0)
+        .stops(&[Color::parse("white").unwrap(), Color::from_hex(0x1E88E5)])
+        .into();
+    let tab = Tab::new().sized(50.0, 80.0).radius(5.0).tab(15.0, 25.0);
+    let first = tab
+        .svg()
+        .filled(&fade, &mut defs)
+        .translated_xy(60.0, -90.0);
+    let second = tab
+        .svg()
+        .filled(&fade, &mut defs)
+        .translated_xy(130.0, -90.0);
+
+    let document = piechart_canvas()
+        .add(defs)
+        .add(chart)
+        .add(first)
+        .add(second);
+    svg::save("/tmp/test_gradients.svg", &document).expect("failed to write svg");
+}
+
+fn make_patterns() {
+    use svg_util::color::Color;
+    use svg_util::defs::{Defs, Painted, Pattern};
+    use svg_util::plot;
+
+    // Black and white only, the patterns tell the segments and bars apart.
+    let white = Color::WHITE;
+    let patterns = [
+        Pattern::diagonal().background(white),
+        Pattern::cross_hatch().spacing(8.0).background(white),
+        Pattern::dots().spacing(5.0).background(white),
+        Pattern::horizontal().stroke_width(2.0).background(white),
+        Pattern::vertical().spacing(4.0).background(white),
+    ];
+    let mut defs = Defs::new();
+
+    let mut pie_chart = PieChart::new();
+    pie_chart.set_radius(70.0);
+    pie_chart.set_segments(&[0.1, 0.15, 0.2, 0.25, 0.3]);
+    for (i, pattern) in patterns.iter().enumerate() {
+        if let Some(s) = pie_chart.segment_mut(i) {
+            s.fill = Some(pattern.clone().into());
+        }
+    }
+    let legend = pie_chart
+        .legend_with_defs(&["A", "B", "C", "D", "E"], &mut defs)
+        .set_label("fill", "white")
+        .svg()
+        .translated_xy(-40.0, -190.0);
+    let chart = pie_chart
+        .svg_with_defs(&mut defs)
+        .translated_xy(-110.0, -100.0);
+
+    let v = plot::AxisVertical::new(150.0);
+    v.set_canvas_range(-75.0, 75.0);
+    v.set_plot_range(0.0, 5.0);
+    let v = v.set("stroke", "white");
+    let h = plot::AxisHorizontal::new(150.0);
+    h.set_canvas_range(-75.0, 75.0);
+    h.set_categories(&["Q1", "Q2", "Q3"]);
+    let h = h.set("stroke", "white").set_tick_label("fill", "white");
+    let f = v.combine(&h);
+    let mut p = plot::Plot::new(&f);
+    let series: [&[f64]; 3] = [&[1.0, 2.0, 3.0], &[2.0, 4.0, 1.0], &[3.0, 1.0, 2.0]];
+    for (handle, pattern) in p.bars_grouped(&series).into_iter().zip(patterns.iter()) {
+        handle.set_fill(pattern.clone()).set("stroke", "black");
+    }
+    let bars = p.svg_with_defs(&mut defs).translated_xy(100.0, 90.0);
+
+    let tab = Tab::new()
+        .sized(50.0, 80.0)
+        .radius(5.0)
+        .tab(15.0, 25.0)
+        .svg()
+        .filled(&patterns[0].clone().into(), &mut defs)
+        .translated_xy(-150.0, 60.0);
+
+    let document = piechart_canvas()
+        .add(defs)
+        .add(chart)
+        .add(legend)
+        .add(bars)
+        .add(tab);
+    svg::save("/tmp/test_patterns.svg", &document).expect("failed to write svg");
+}
+
 fn make_tab() {
     // let mut tab = Tab::new();
 
@@ -277,38 +498,215 @@ fn make_tab() {
             .translated_xy(80.0, 110.0),
     );
 
+    let rotated = Tab::new()
+        .sized(50.0, 80.0)
+        .radius(5.0)
+        .tab(10.0, 20.0)
+        .tab_position(40.0)
+        .tab_edge(TabEdge::None)
+        .svg()
+        .set("stroke", "yellow")
+        .set("fill", "none")
+        .translated_xy(-80.0, 110.0)
+        .rotated(45);
+    // Outline where the rotated tab ends up on the canvas.
+    let placement = rotated.affine().expect("transform should parse");
+    let ((min_x, min_y), (max_x, max_y)) = placement.apply_bounds((0.0, 0.0), (50.0, 80.0));
+    let outline = svg::node::element::Rectangle::new()
+        .set("x", min_x)
+        .set("y", min_y)
+        .set("width", max_x - min_x)
+        .set("height", max_y - min_y)
+        .set("stroke", "gray")
+        .set("stroke-dasharray", 2)
+        .set("fill", "none");
+    let document = document.add(rotated).add(outline);
+    // A mirrored copy of the first tab, the protrusion ends up on the right.
+    let document = document.add(
+        Tab::new()
+            .sized(50.0, 80.0)
+            .radius(10.0)
+            .tab(15.0, 25.0)
+            .tab_position(15.0)
+            .tab_edge(TabEdge::Left)
+            .svg()
+            .set("stroke", "orange")
+            .set("fill", "none")
+            .translated_xy(-90.0, 0.0)
+            .flipped_horizontal(),
+    );
+    // A rounded tab on a square body, like the UI mock-ups.
     let document = document.add(
         Tab::new()
             .sized(50.0, 80.0)
             .radius(5.0)
-            .tab(10.0, 20.0)
-            .tab_position(40.0)
-            .tab_edge(TabEdge::None)
+            .body_corners(Corner::sharp())
+            .tab(15.0, 25.0)
+            .tab_position(15.0)
+            .tab_edge(TabEdge::Left)
             .svg()
-            .set("stroke", "yellow")
+            .set("stroke", "cyan")
+            .set("fill", "none")
+            .translated_xy(-180.0, -180.0),
+    );
+    // Chamfered corners, except for the top right corner and the inner fillets.
+    let document = document.add(
+        Tab::new()
+            .sized(50.0, 80.0)
+            .corners(Corner::chamfer(5.0))
+            .body_corner(BodyCorner::TopRight, Corner::round(15.0))
+            .fillet_corners(Corner::round(3.0))
+            .tab(20.0, 10.0)
+            .tab_position(20.0)
+            .tab_edge(TabEdge::Bottom)
+            .svg()
+            .set("stroke", "cyan")
             .set("fill", "none")
-            .translated_xy(-80.0, 110.0)
-            .rotated(45),
+            .translated_xy(150.0, -180.0),
     );
 
     svg::save("/tmp/test_tab.svg", &document).expect("failed to write svg");
 }
 
+fn make_bounding_boxes() {
+    use svg::node::element::Rectangle;
+    use svg_util::bbox::{BoundingBox, Rect};
+    use svg_util::color::Color;
+    use svg_util::document::Canvas;
+    use svg_util::pie_chart::LabelFormat;
+
+    let outline = |r: Rect| {
+        Rectangle::new()
+            .set("x", r.min_x)
+            .set("y", r.min_y)
+            .set("width", r.width())
+            .set("height", r.height())
+            .set("stroke", "gray")
+            .set("stroke-dasharray", 2)
+            .set("fill", "none")
+    };
+
+    let mut pie_chart = PieChart::from_values(&[
+        ("Compute", 5000.0),
+        ("Storage", 3000.0),
+        ("Network", 1000.0),
+    ])
+    .expect("values should be valid");
+    pie_chart.set_radius(100.0);
+    pie_chart.set_label_format(LabelFormat::Label);
+    let pie = pie_chart.svg();
+    let pie_bounds = pie.bounding_box().expect("chart is not empty");
+
+    // Tabs placed to the right of the chart, each one next to the previous one.
+    let mut group = Group::new().add(pie).add(outline(pie_bounds));
+    let mut right = pie_bounds.max_x + 20.0;
+    for edge in [TabEdge::Left, TabEdge::Top, TabEdge::Bottom] {
+        let tab = Tab::new()
+            .sized(50.0, 80.0)
+            .radius(5.0)
+            .tab(10.0, 20.0)
+            .tab_position(20.0)
+            .tab_edge(edge);
+        let bounds = tab.bounding_box().expect("tab is not empty");
+        let placed = tab
+            .svg()
+            .set("stroke", "yellow")
+            .set("fill", "none")
+            .translated_xy(right - bounds.min_x, pie_bounds.min_y - bounds.min_y);
+        let placed_bounds = placed.bounding_box().expect("tab is not empty");
+        right = placed_bounds.max_x + 20.0;
+        group = group.add(outline(placed_bounds)).add(placed);
+    }
+
+    // The viewBox fits everything with a margin around it.
+    let document = Canvas::new()
+        .add(group)
+        .margin(10.0)
+        .scale(4.0)
+        .background(Color::BLACK)
+        .svg();
+    svg::save("/tmp/test_bounding_boxes.svg", &document).expect("failed to write svg");
+}
+
+fn make_layout() {
+    use svg_util::color::Color;
+    use svg_util::document::Canvas;
+    use svg_util::layout::{Align, Flow, Grid, Stack};
+
+    let tab = |edge: TabEdge, height: f64| {
+        Tab::new()
+            .sized(50.0, height)
+            .radius(5.0)
+            .tab(10.0, 20.0)
+            .tab_position(15.0)
+            .tab_edge(edge)
+            .svg()
+            .set("stroke", "yellow")
+            .set("fill", "none")
+    };
+    let edges = [TabEdge::Left, TabEdge::Top, TabEdge::Right, TabEdge::Bottom];
+
+    // One tab per edge, centered in their cells.
+    let mut grid = Grid::new(2).gap(10.0).align(Align::Center, Align::Center);
+    for edge in edges {
+        grid = grid.add(tab(edge, 60.0));
+    }
+
+    // Tabs of increasing height, wrapping after three and aligned to the bottom of their line.
+    let mut flow = Flow::new(220.0).gap(10.0).align(Align::End);
+    for (i, edge) in edges.iter().cycle().take(7).enumerate() {
+        flow = flow.add(tab(*edge, 50.0 + 10.0 * i as f64));
+    }
+
+    let mut pie_chart = PieChart::new();
+    pie_chart.set_radius(50.0);
+    pie_chart.set_segments(&[0.15, 0.3, 0.4, 0.15]);
+    let legend = pie_chart
+        .legend(&["Rent", "Food", "Travel", "Other"])
+        .set_label("fill", "white");
+    let chart = Stack::horizontal()
+        .gap(10.0)
+        .align(Align::Center)
+        .add(pie_chart.clone())
+        .add(legend.svg());
+
+    let layout = Stack::vertical()
+        .gap(20.0)
+        .padding(10.0)
+        .add(Stack::horizontal().gap(20.0).add(grid).add(flow))
+        .add(chart);
+    let document = Canvas::new()
+        .add(layout)
+        .scale(4.0)
+        .background(Color::BLACK)
+        .svg();
+    svg::save("/tmp/test_layout.svg", &document).expect("failed to write svg");
+}
+
 fn make_plot() {
+    use svg_util::legend::LegendOrientation;
     use svg_util::plot;
-    /*
-    use svg::node::element::path::Data;
-    use svg::node::element::Path;
-
 
-    let mut v = plot::AxisVertical::new(500.0);
+    let v = plot::AxisVertical::new(500.0);
     v.set_canvas_range(-100.0, 100.0);
     v.set_plot_range(-1.0, 1.0);
-    let v = v.set("stroke", "black").set("stroke-width", 3.0);
-    let mut h = plot::AxisHorizontal::new(100.0);
+    let v = v
+        .set("stroke", "black")
+        .set("stroke-width", 3.0)
+        .set_tick_label("font-size", 8);
+    let h = plot::AxisHorizontal::new(100.0);
     h.set_canvas_range(-100.0, 100.0);
     h.set_plot_range(0.0, 6.5);
-    let h = h.set("stroke", "black").set("stroke-width", 3.0);
+    h.set_ticks(plot::Ticks::Labeled(vec![
+        (PI / 2.0, "π/2".to_owned()),
+        (PI, "π".to_owned()),
+        (3.0 * PI / 2.0, "3π/2".to_owned()),
+        (2.0 * PI, "2π".to_owned()),
+    ]));
+    let h = h
+        .set("stroke", "black")
+        .set("stroke-width", 3.0)
+        .set_tick_label("font-size", 8);
     let f = v.combine(&h);
     let mut p = plot::Plot::new(&f);
     let mut data = vec![];
@@ -316,41 +714,143 @@ fn make_plot() {
         let t = i as f64 / 100.0;
         data.push((t, t.sin()));
     }
-    let mut el = p.line_xy(&data)
+    p.line_xy(&data)
         .set("fill", "none")
         .set("stroke", "navy")
-        .set("stroke-width", "5");
-
-    // p.add_frame(&h, &v);
+        .set("stroke-width", "5")
+        .set_label("sin(t)");
+
+    let samples: Vec<(f64, f64)> = (0..13)
+        .map(|i| i as f64 / 2.0)
+        .map(|t| (t, t.cos()))
+        .collect();
+    let sizes: Vec<f64> = (0..13).map(|i| 4.0 + i as f64).collect();
+    p.scatter_xy(&samples)
+        .set("fill", "orange")
+        .set_marker(plot::Marker::Diamond)
+        .set_marker_sizes(&sizes)
+        .set_marker_colors(&["red", "green", "blue"])
+        .set_label("cos(t)");
+
+    let legend = p
+        .legend()
+        .orientation(LegendOrientation::Horizontal)
+        .svg()
+        .translated_xy(-100.0, 120.0);
 
     let mut group = Group::new();
     group.append(p);
-
-    /*
-    let data = Data::new()
-        .move_to((-1000, 1000))
-        .line_to((1000, 1000))
-        .line_to((1000, -1000))
-        .line_to((-1000, -1000))
-        .close();
-    let path = Path::new().set("fill", "black").set("d", data);
-    group.append(path);
-    */
+    group.append(legend);
 
     let document = Document::new()
         .set("viewBox", (-200, -200, 400, 400)) // from -200,-200, width and height of 400.
         .set("width", "2000px")
         .set("height", "2000px")
-        // .add(path)
         .add(group);
 
     svg::save("/tmp/test_plot.svg", &document).expect("failed to write svg");
-    */
+}
+
+fn make_plot_log() {
+    use svg_util::plot;
+
+    let v = plot::AxisVertical::new(200.0);
+    v.set_canvas_range(-100.0, 100.0);
+    v.set_plot_range(0.1, 10000.0);
+    v.set_scale(plot::Scale::log10());
+    let v = v
+        .set("stroke", "black")
+        .set("stroke-width", 1.0)
+        .set_tick_label("font-size", 8);
+    let h = plot::AxisHorizontal::new(200.0);
+    h.set_canvas_range(-100.0, 100.0);
+    h.set_plot_range(-100.0, 100.0);
+    h.set_scale(plot::Scale::symlog(1.0));
+    let h = h
+        .set("stroke", "black")
+        .set("stroke-width", 1.0)
+        .set_tick_label("font-size", 8);
+    let f = v.combine(&h);
+    let mut p = plot::Plot::new(&f);
+    let data: Vec<(f64, f64)> = (-100..=100)
+        .map(|i| i as f64)
+        .map(|x| (x, 0.1 + x * x))
+        .collect();
+    p.line_xy(&data)
+        .set("fill", "none")
+        .set("stroke", "navy")
+        .set("stroke-width", "2");
+
+    let document = Document::new()
+        .set("viewBox", (-200, -200, 400, 400)) // from -200,-200, width and height of 400.
+        .set("width", "2000px")
+        .set("height", "2000px")
+        .add(p);
+
+    svg::save("/tmp/test_plot_log.svg", &document).expect("failed to write svg");
+}
+
+fn make_bar_chart() {
+    use svg_util::document::{Canvas, Unit};
+    use svg_util::palette::Palette;
+    use svg_util::plot;
+
+    let v = plot::AxisVertical::new(200.0);
+    v.set_canvas_range(-100.0, 100.0);
+    v.set_plot_range(0.0, 10.0);
+    let v = v
+        .set("stroke", "black")
+        .set("stroke-width", 1.0)
+        .set_tick_label("font-size", 8);
+    let h = plot::AxisHorizontal::new(200.0);
+    h.set_canvas_range(-100.0, 100.0);
+    h.set_categories(&["Mon", "Tue", "Wed", "Thu", "Fri"]);
+    let h = h
+        .set("stroke", "black")
+        .set("stroke-width", 1.0)
+        .set_tick_label("font-size", 8);
+    let f = v.combine(&h);
+
+    let mut p = plot::Plot::new(&f);
+    let series: [&[f64]; 3] = [
+        &[1.0, 2.0, 3.0, 2.0, 1.0],
+        &[2.0, 1.0, 2.0, 3.0, 4.0],
+        &[3.0, 3.0, 1.0, 1.0, 2.0],
+    ];
+    let colors = ["#D81B60", "#1E88E5", "#FFC107"];
+    for (handle, color) in p.bars_stacked(&series).into_iter().zip(colors) {
+        handle.set("fill", color);
+    }
+
+    let mut grouped = plot::Plot::new(&f);
+    grouped.set_bar_width(0.6);
+    grouped.set_palette(Palette::okabe_ito());
+    for handle in grouped.bars_grouped(&series) {
+        handle.set("fill-opacity", 0.5);
+    }
+
+    // Sized to fit, printed 10cm wide.
+    let document = Canvas::new()
+        .add(p)
+        .margin(10.0)
+        .unit(Unit::Mm)
+        .width(100.0)
+        .svg();
+    svg::save("/tmp/test_bar_chart.svg", &document).expect("failed to write svg");
+
+    // Sized to fit, three pixels per user unit, expressed in inches at 150 dpi.
+    let document = Canvas::new()
+        .add(grouped)
+        .margin(10.0)
+        .scale(3.0)
+        .unit(Unit::In)
+        .dpi(150.0)
+        .svg();
+    svg::save("/tmp/test_bar_chart_grouped.svg", &document).expect("failed to write svg");
 }
 
 fn make_flow_elements() {
-    use svg::node::element::path::Data;
-    use svg::node::element::{Path, Rectangle, Text};
+    use svg::node::element::{Rectangle, Text};
     use svg_util::extensions::*;
 
     let mut root = FlowRoot::new().set("style", "text-anchor:start;text-align:justify;");
@@ -358,7 +858,7 @@ fn make_flow_elements() {
     let mut region = FlowRegion::new().set("style", "text-anchor:start;text-align:justify;");
     let mut para = FlowPara::new();
 
-    let mut r = Rectangle::new().set("width", 300).set("height", 300);
+    let r = Rectangle::new().set("width", 300).set("height", 300);
     region.append(r);
 
     let t = Text::new("Lorem ipsum odor amet, consectetuer adipiscing elit. Magnis integer ipsum purus blandit lacus risus dapibus vulputate consectetur. Primis dui fermentum mattis; felis iaculis ultrices phasellus. Suspendisse vitae pharetra quis dis inceptos penatibus. Lacus suscipit efficitur potenti nunc sed. Tempor finibus litora congue hendrerit scelerisque sed dignissim. Senectus duis taciti ornare dui porttitor vulputate vivamus. Scelerisque ullamcorper praesent natoque dictum mollis orci mattis vestibulum.");
@@ -380,12 +880,9 @@ fn make_flow_elements() {
 }
 
 fn make_flow_text() {
-    use svg::node::element::path::Data;
-    use svg::node::element::{Path, Rectangle, Text};
-    use svg_util::extensions::*;
     use svg_util::flow_text::FlowText;
     let blob = "Lorem ipsum odor amet, consectetuer adipiscing elit. Magnis integer ipsum purus blandit lacus risus dapibus vulputate consectetur. Primis dui fermentum mattis; felis iaculis ultrices phasellus. Suspendisse vitae pharetra quis dis inceptos penatibus.\nLacus suscipit efficitur potenti nunc sed. Tempor finibus litora congue hendrerit scelerisque sed dignissim. Senectus duis taciti ornare dui porttitor vulputate vivamus. Scelerisque ullamcorper praesent natoque dictum mollis orci mattis vestibulum.";
-    let mut flow_text = FlowText::rectangle(300.0, 300.0, blob)
+    let flow_text = FlowText::rectangle(300.0, 300.0, blob)
         .set("style", "text-anchor:start;text-align:justify;");
 
     let mut group = Group::new();
@@ -406,8 +903,18 @@ fn make_flow_text() {
 fn main() {
     make_piechart();
     make_piechart_align_largest();
+    make_donut();
+    make_piechart_labels();
+    make_gauge();
+    make_sunburst();
+    make_gradients();
+    make_patterns();
     make_tab();
+    make_bounding_boxes();
+    make_layout();
     make_plot();
+    make_plot_log();
+    make_bar_chart();
     make_flow_elements();
     make_flow_text();
 }
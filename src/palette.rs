@@ -0,0 +1,141 @@
+use crate::color::Color;
+
+/// How a palette assigns colors to a number of items.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PaletteKind {
+    /// Distinct colors for unordered categories, repeated when there are more items than colors.
+    Qualitative,
+    /// A ramp from light to dark, or the reverse, for ordered data.
+    Sequential,
+    /// A ramp with a neutral center, for data that deviates in two directions from a midpoint.
+    Diverging,
+}
+
+/// A list of colors, either used as distinct colors or interpolated as a ramp.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    kind: PaletteKind,
+    colors: Vec<Color>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        // https://davidmathlogic.com/colorblind/, extended with more material colors such that
+        // charts with up to ten categories do not repeat a color.
+        Palette::qualitative(&hex(&[
+            0xD81B60, 0x1E88E5, 0xFFC107, 0x004D40, 0x5E35B1, 0x43A047, 0xF4511E, 0x00ACC1,
+            0x6D4C41, 0xC0CA33,
+        ]))
+    }
+}
+
+impl Palette {
+    /// A palette of distinct colors, used in order and repeated.
+    pub fn qualitative(colors: &[Color]) -> Self {
+        Palette {
+            kind: PaletteKind::Qualitative,
+            colors: colors.to_vec(),
+        }
+    }
+
+    /// A ramp through the provided colors, which are equally spaced.
+    pub fn sequential(stops: &[Color]) -> Self {
+        Palette {
+            kind: PaletteKind::Sequential,
+            colors: stops.to_vec(),
+        }
+    }
+
+    /// A ramp from low through mid to high.
+    pub fn diverging(low: Color, mid: Color, high: Color) -> Self {
+        Palette {
+            kind: PaletteKind::Diverging,
+            colors: vec![low, mid, high],
+        }
+    }
+
+    /// The Okabe-Ito palette, eight colors that remain distinct for common color blindness.
+    pub fn okabe_ito() -> Self {
+        Palette::qualitative(&hex(&[
+            0xE69F00, 0x56B4E9, 0x009E73, 0xF0E442, 0x0072B2, 0xD55E00, 0xCC79A7, 0x000000,
+        ]))
+    }
+
+    /// Paul Tol's bright palette, seven color blind safe colors.
+    pub fn tol_bright() -> Self {
+        Palette::qualitative(&hex(&[
+            0x4477AA, 0xEE6677, 0x228833, 0xCCBB44, 0x66CCEE, 0xAA3377, 0xBBBBBB,
+        ]))
+    }
+
+    /// The IBM design library palette, five color blind safe colors.
+    pub fn ibm() -> Self {
+        Palette::qualitative(&hex(&[0x648FFF, 0x785EF0, 0xDC267F, 0xFE6100, 0xFFB000]))
+    }
+
+    /// The viridis ramp, from dark purple to yellow, perceptually uniform.
+    pub fn viridis() -> Self {
+        Palette::sequential(&hex(&[0x440154, 0x3B528B, 0x21918C, 0x5EC962, 0xFDE725]))
+    }
+
+    /// A ramp from light to dark blue.
+    pub fn blues() -> Self {
+        Palette::sequential(&hex(&[0xF7FBFF, 0xC6DBEF, 0x6BAED6, 0x2171B5, 0x08306B]))
+    }
+
+    /// A ramp from red through white to blue.
+    pub fn red_blue() -> Self {
+        Palette {
+            kind: PaletteKind::Diverging,
+            colors: hex(&[0xB2182B, 0xEF8A62, 0xF7F7F7, 0x67A9CF, 0x2166AC]),
+        }
+    }
+
+    /// The kind of this palette.
+    pub fn kind(&self) -> PaletteKind {
+        self.kind
+    }
+
+    /// The color for an item out of count items.
+    ///
+    /// Qualitative palettes repeat their colors, ramps are sampled evenly from end to end.
+    pub fn color(&self, index: usize, count: usize) -> Color {
+        if self.colors.is_empty() {
+            return Color::BLACK;
+        }
+        match self.kind {
+            PaletteKind::Qualitative => self.colors[index % self.colors.len()],
+            PaletteKind::Sequential | PaletteKind::Diverging => {
+                if count <= 1 {
+                    // A single item takes the center, which is neutral for diverging ramps.
+                    self.sample(0.5)
+                } else {
+                    self.sample(index as f64 / (count - 1) as f64)
+                }
+            }
+        }
+    }
+
+    /// The colors for count items.
+    pub fn colors(&self, count: usize) -> Vec<Color> {
+        (0..count).map(|i| self.color(i, count)).collect()
+    }
+
+    /// The color at a position between 0 and 1 along the palette, interpolated between colors.
+    pub fn sample(&self, t: f64) -> Color {
+        if self.colors.is_empty() {
+            return Color::BLACK;
+        }
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let scaled = t * (self.colors.len() - 1) as f64;
+        let index = (scaled.floor() as usize).min(self.colors.len() - 1);
+        match self.colors.get(index + 1) {
+            Some(next) => self.colors[index].mix(*next, scaled - index as f64),
+            None => self.colors[index],
+        }
+    }
+}
+
+fn hex(colors: &[u32]) -> Vec<Color> {
+    colors.iter().map(|c| Color::from_hex(*c)).collect()
+}
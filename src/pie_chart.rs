@@ -1,26 +1,355 @@
+use crate::color::Color;
+use crate::defs::{Defs, Paint};
+use crate::legend::{Legend, LegendEntry};
+use crate::palette::Palette;
+use std::borrow::Cow;
 use std::f64::consts::PI;
-use svg::node::element::{path::Data, Group, Path};
+use std::rc::Rc;
+use svg::node::element::{path::Data, Circle, Group, Path, Polyline, Text};
+use svg::node::{Attributes, Value};
+use svg::Node;
 
-// https://davidmathlogic.com/colorblind/
-const FALLBACK_COLORS: [&str; 4] = ["#D81B60", "#1E88E5", "#FFC107", "#004D40"];
+/// Path data for a full circle, or ring if the inner radius is non-zero.
+///
+/// An arc with identical start and end points draws nothing, so circles are split into two arcs.
+fn circle_data(center: (f64, f64), inner_radius: f64, outer_radius: f64, start: f64) -> Data {
+    let (cx, cy) = center;
+    let mut data = Data::new();
+    for (radius, sweep) in [(outer_radius, 1), (inner_radius, 0)] {
+        if radius <= 0.0 {
+            continue;
+        }
+        let sx = cx + start.cos() * radius;
+        let sy = cy + start.sin() * radius;
+        let mx = cx + (start + PI).cos() * radius;
+        let my = cy + (start + PI).sin() * radius;
+        // Inner circle runs in the opposite direction, such that it cuts a hole in the outer one.
+        data = data
+            .move_to((sx, sy))
+            .elliptical_arc_to((radius, radius, 0.0, 0, sweep, mx, my))
+            .elliptical_arc_to((radius, radius, 0.0, 0, sweep, sx, sy))
+            .close();
+    }
+    data
+}
+
+/// Path data for a sector of a circle centered at center, spanning angle radians from start.
+///
+/// If the inner radius is non-zero this is an annular sector, a segment of a ring.
+fn sector_data(
+    center: (f64, f64),
+    inner_radius: f64,
+    outer_radius: f64,
+    start: f64,
+    angle: f64,
+) -> Data {
+    // Tolerance for ratios that should sum to one, but don't quite due to rounding.
+    if angle >= 2.0 * PI - 1e-9 {
+        return circle_data(center, inner_radius, outer_radius, start);
+    }
+    let large_arc = if angle > PI { 1 } else { 0 };
+    let (cx, cy) = center;
+    let arc_sx = cx + start.cos() * outer_radius;
+    let arc_sy = cy + start.sin() * outer_radius;
+    let arc_ex = cx + (angle + start).cos() * outer_radius;
+    let arc_ey = cy + (angle + start).sin() * outer_radius;
+    if inner_radius <= 0.0 {
+        return Data::new()
+            .move_to(center) // all circles start in the center
+            .line_to((arc_sx, arc_sy))
+            .elliptical_arc_by((
+                outer_radius,
+                outer_radius,
+                0.0, // x axis rotation of the ellipse
+                large_arc,
+                1, // large flag arc, sweep flag
+                arc_ex - arc_sx,
+                arc_ey - arc_sy,
+            ))
+            .line_to((arc_ex, arc_ey))
+            .close();
+    }
+    let inner_sx = cx + start.cos() * inner_radius;
+    let inner_sy = cy + start.sin() * inner_radius;
+    let inner_ex = cx + (angle + start).cos() * inner_radius;
+    let inner_ey = cy + (angle + start).sin() * inner_radius;
+    Data::new()
+        .move_to((arc_sx, arc_sy))
+        .elliptical_arc_by((
+            outer_radius,
+            outer_radius,
+            0.0, // x axis rotation of the ellipse
+            large_arc,
+            1, // large flag arc, sweep flag
+            arc_ex - arc_sx,
+            arc_ey - arc_sy,
+        ))
+        .line_to((inner_ex, inner_ey))
+        .elliptical_arc_by((
+            inner_radius,
+            inner_radius,
+            0.0, // x axis rotation of the ellipse
+            large_arc,
+            0, // large flag arc, sweep flag, inner arc runs back
+            inner_sx - inner_ex,
+            inner_sy - inner_ey,
+        ))
+        .close()
+}
 
+/// A single segment of a pie chart.
+///
+/// Create segments with [`PieSegment::new`] or from a ratio, more fields may be added over time.
 #[derive(Debug, Clone, Default)]
+#[non_exhaustive]
 pub struct PieSegment {
     /// The ratio this segment depicts, between 0.0 and 1.0.
     pub ratio: f64,
-    /// The color to use for this segment, if empty fallback colors are used.
-    pub color: String,
+    /// The color to use for this segment, if none the chart's palette is used.
+    pub color: Option<Color>,
+    /// Paint to fill this segment with instead of its color, like a gradient.
+    pub fill: Option<Paint>,
+    /// The label of this segment, if empty no label is drawn.
+    pub label: String,
+    /// Distance to move this segment outward along its bisector, if None the chart's default is
+    /// used.
+    pub explode: Option<f64>,
+    /// The absolute value this segment was created from, if any.
+    pub value: Option<f64>,
 }
 
-impl From<f64> for PieSegment {
-    fn from(ratio: f64) -> Self {
+impl PieSegment {
+    /// Create a segment depicting the provided ratio.
+    pub fn new(ratio: f64) -> Self {
         PieSegment {
             ratio,
-            color: String::new(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the segment with the label set to the provided value.
+    pub fn label<T: Into<String>>(mut self, label: T) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Returns the segment with the color set to the provided value.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Returns the segment filled with the provided paint instead of its color.
+    pub fn fill<T: Into<Paint>>(mut self, paint: T) -> Self {
+        self.fill = Some(paint.into());
+        self
+    }
+
+    /// Returns the segment moved outward by the provided distance.
+    pub fn explode(mut self, distance: f64) -> Self {
+        self.explode = Some(distance);
+        self
+    }
+
+    /// Returns the segment with the absolute value it depicts set.
+    pub fn value(mut self, value: f64) -> Self {
+        self.value = Some(value);
+        self
+    }
+}
+
+impl From<f64> for PieSegment {
+    fn from(ratio: f64) -> Self {
+        PieSegment::new(ratio)
+    }
+}
+
+impl From<(f64, &str)> for PieSegment {
+    fn from((ratio, label): (f64, &str)) -> Self {
+        PieSegment::new(ratio).label(label)
+    }
+}
+
+/// Merges small segments into a single segment, to prevent lots of unreadable slivers.
+#[derive(Debug, Clone)]
+pub struct OtherBucket {
+    /// Segments with a ratio below this threshold are merged.
+    pub threshold: Option<f64>,
+    /// Segments beyond the largest this many are merged.
+    pub top_n: Option<usize>,
+    /// The label of the merged segment.
+    pub label: String,
+    /// The color of the merged segment.
+    pub color: Color,
+}
+
+impl Default for OtherBucket {
+    fn default() -> Self {
+        Self {
+            threshold: None,
+            top_n: None,
+            label: "Other".to_owned(),
+            color: Color::from_hex(0x9E9E9E),
+        }
+    }
+}
+
+impl OtherBucket {
+    /// Merge all segments with a ratio below the threshold.
+    pub fn threshold(threshold: f64) -> Self {
+        Self {
+            threshold: Some(threshold),
+            ..Default::default()
+        }
+    }
+
+    /// Merge all segments except the largest n.
+    pub fn top(n: usize) -> Self {
+        Self {
+            top_n: Some(n),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a bucket with the label set to the provided value.
+    pub fn label<T: Into<String>>(mut self, label: T) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Returns a bucket with the color set to the provided value.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Whether the segment should be merged, rank is its position when sorted by size.
+    fn merges(&self, segment: &PieSegment, rank: usize) -> bool {
+        self.threshold.is_some_and(|t| segment.ratio < t) || self.top_n.is_some_and(|n| rank >= n)
+    }
+}
+
+/// Errors from validating the values or ratios of a pie chart.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PieChartError {
+    /// The value at this index is negative.
+    Negative(usize),
+    /// The value at this index is NaN or infinite.
+    NonFinite(usize),
+    /// There are no values, or all of them are zero.
+    AllZero,
+    /// The ratios sum to this value, which is not allowed by the sum policy.
+    InvalidSum(f64),
+}
+
+impl std::fmt::Display for PieChartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PieChartError::Negative(i) => write!(f, "value at index {} is negative", i),
+            PieChartError::NonFinite(i) => write!(f, "value at index {} is not finite", i),
+            PieChartError::AllZero => write!(f, "no non-zero values"),
+            PieChartError::InvalidSum(sum) => write!(f, "ratios sum to {}", sum),
         }
     }
 }
 
+impl std::error::Error for PieChartError {}
+
+/// What to do with ratios that do not sum to one.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum SumPolicy {
+    /// Scale the ratios such that they sum to one.
+    #[default]
+    Normalize,
+    /// Keep the ratios, a sum below one leaves a gap at the end, a sum above one is an error.
+    Gap,
+    /// Ratios that do not sum to one are an error.
+    Error,
+}
+
+/// Tolerance on the sum of ratios, to allow for rounding errors.
+const SUM_TOLERANCE: f64 = 1e-9;
+
+/// Check that all values are finite and non-negative, and that not all are zero, returns the sum.
+fn validate(values: impl Iterator<Item = f64>) -> Result<f64, PieChartError> {
+    let mut sum = 0.0;
+    for (i, v) in values.enumerate() {
+        if !v.is_finite() {
+            return Err(PieChartError::NonFinite(i));
+        }
+        if v < 0.0 {
+            return Err(PieChartError::Negative(i));
+        }
+        sum += v;
+    }
+    if sum == 0.0 {
+        return Err(PieChartError::AllZero);
+    }
+    Ok(sum)
+}
+
+/// Determines the text of the segment labels.
+#[derive(Clone, Default)]
+pub enum LabelFormat {
+    /// No labels are drawn.
+    None,
+    /// The segment's label, segments without a label get none.
+    #[default]
+    Label,
+    /// The segment's ratio as a percentage, with the provided number of decimals.
+    Percentage(usize),
+    /// The segment's label followed by its percentage, with the provided number of decimals.
+    LabelPercentage(usize),
+    /// The segment's absolute value, with the provided number of decimals, if it has one.
+    Value(usize),
+    /// A user provided formatter, empty strings are not drawn.
+    Custom(Rc<dyn Fn(&PieSegment) -> String>),
+}
+
+impl std::fmt::Debug for LabelFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelFormat::None => f.write_str("None"),
+            LabelFormat::Label => f.write_str("Label"),
+            LabelFormat::Percentage(d) => f.debug_tuple("Percentage").field(d).finish(),
+            LabelFormat::LabelPercentage(d) => f.debug_tuple("LabelPercentage").field(d).finish(),
+            LabelFormat::Value(d) => f.debug_tuple("Value").field(d).finish(),
+            LabelFormat::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+impl LabelFormat {
+    /// The label text for the segment.
+    pub fn format(&self, segment: &PieSegment) -> String {
+        match self {
+            LabelFormat::None => String::new(),
+            LabelFormat::Label => segment.label.clone(),
+            LabelFormat::Percentage(d) => format!("{:.*}%", d, segment.ratio * 100.0),
+            LabelFormat::LabelPercentage(d) if segment.label.is_empty() => {
+                format!("{:.*}%", d, segment.ratio * 100.0)
+            }
+            LabelFormat::LabelPercentage(d) => {
+                format!("{} {:.*}%", segment.label, d, segment.ratio * 100.0)
+            }
+            LabelFormat::Value(d) => segment
+                .value
+                .map(|v| format!("{:.*}", d, v))
+                .unwrap_or_default(),
+            LabelFormat::Custom(f) => f(segment),
+        }
+    }
+}
+
+/// A label that is placed outside of the chart, on one side of it.
+struct OutsideLabel {
+    index: usize,
+    text: String,
+    angle: f64,
+    offset: (f64, f64),
+    y: f64,
+}
+
 /// Style for positioning of the start segment.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum StartStyle {
@@ -32,20 +361,152 @@ pub enum StartStyle {
     CenterLargest,
 }
 
+/// The angle at which the first of the ratios starts, taking the start style into account.
+///
+/// The ratios are fractions of the sweep, the total angle covered by the chart.
+fn start_position(ratios: &[f64], offset: f64, style: StartStyle, sweep: f64) -> f64 {
+    let mut current_pos: f64 = offset;
+
+    // If we have a centered style, subtract by half of the first ratio.
+    match style {
+        StartStyle::Edge => {}
+        StartStyle::Center => {
+            if let Some(s) = ratios.first() {
+                current_pos -= (s / 2.0) * sweep;
+            }
+        }
+        StartStyle::CenterLargest => {
+            // Approach:
+            // Assume all segments are equal.
+            // Align the largest part to its centered-equal direction.
+            let ideal = 1.0f64 / (ratios.len() as f64);
+            // https://stackoverflow.com/a/53908709
+            let max_segment: Option<(usize, &f64)> = ratios
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b));
+            if let Some((max_index, max_segment)) = max_segment {
+                // Calculate position of the center of the max segment in ideal position.
+                let max_segment_ideal = max_index as f64 * ideal + ideal / 2.0;
+                // Calculate the position of the center of the max segment in reality, accumulating ratios.
+                let max_segment_real = ratios[..max_index].iter().sum::<f64>() + max_segment / 2.0;
+                // Ofset it by the difference.
+                current_pos = (max_segment_ideal - max_segment_real) * sweep;
+            }
+        }
+    }
+    current_pos
+}
+
+/// A pointer from the center of a chart towards a position along its sweep, as on a gauge.
+#[derive(Debug, Clone)]
+pub struct Needle {
+    position: f64,
+    length: Option<f64>,
+    width: f64,
+    fill: Paint,
+    attributes: Attributes,
+}
+
+impl Needle {
+    /// Create a needle pointing at a fraction of the sweep, 0 at the start and 1 at the end.
+    pub fn new(position: f64) -> Self {
+        Needle {
+            position,
+            length: None,
+            width: 6.0,
+            fill: Color::WHITE.into(),
+            attributes: Default::default(),
+        }
+    }
+
+    /// Returns a needle with the length set, by default it reaches the outer radius.
+    pub fn length(mut self, length: f64) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Returns a needle with the width at its base set to the provided value.
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Returns a needle filled with the provided paint, white by default.
+    pub fn fill<T: Into<Paint>>(mut self, paint: T) -> Self {
+        self.fill = paint.into();
+        self
+    }
+
+    /// Set an attribute on the needle, like its stroke.
+    pub fn set<T, U>(mut self, name: T, value: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<Value>,
+    {
+        self.attributes.insert(name.into(), value.into());
+        self
+    }
+
+    /// A tapered needle from the origin at the provided angle, with a round hub at its base.
+    fn svg(&self, angle: f64, length: f64, defs: &mut Defs) -> Group {
+        let hw = self.width / 2.0;
+        let (dx, dy) = (angle.cos(), angle.sin());
+        let data = Data::new()
+            .move_to((-dy * hw, dx * hw))
+            .line_to((dx * length, dy * length))
+            .line_to((dy * hw, -dx * hw))
+            .close();
+        let mut group = Group::new()
+            .set("fill", self.fill.resolve(defs))
+            .add(Path::new().set("d", data))
+            .add(Circle::new().set("r", self.width));
+        let attr = group.get_attributes_mut().unwrap();
+        for (k, v) in self.attributes.iter() {
+            attr.insert(k.clone(), v.clone());
+        }
+        group
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PieChart {
     segments: Vec<PieSegment>,
     radius: f64,
+    inner_radius: f64,
     start_offset: f64,
     start_style: StartStyle,
+    sweep: f64,
+    end: Option<f64>,
+    needle: Option<Needle>,
+    center_caption: Option<Box<dyn svg::Node>>,
+    label_format: LabelFormat,
+    label_font_size: f64,
+    label_attributes: Attributes,
+    leader_length: Option<f64>,
+    explode: f64,
+    other: Option<OtherBucket>,
+    palette: Palette,
 }
 impl Default for PieChart {
     fn default() -> Self {
         Self {
             segments: vec![],
             radius: 1.0,
+            inner_radius: 0.0,
             start_offset: 0.0,
             start_style: StartStyle::Edge,
+            sweep: 2.0 * PI,
+            end: None,
+            needle: None,
+            center_caption: None,
+            label_format: Default::default(),
+            label_font_size: 10.0,
+            label_attributes: Default::default(),
+            leader_length: None,
+            explode: 0.0,
+            other: None,
+            palette: Default::default(),
         }
     }
 }
@@ -55,17 +516,118 @@ impl PieChart {
         Default::default()
     }
 
+    /// Create a chart from labeled absolute values, the ratios are the values normalised by
+    /// their sum.
+    pub fn from_values<T: Into<String> + Clone>(
+        values: &[(T, f64)],
+    ) -> Result<Self, PieChartError> {
+        let sum = validate(values.iter().map(|(_, v)| *v))?;
+        let mut chart = PieChart::new();
+        chart.segments = values
+            .iter()
+            .map(|(label, value)| PieSegment {
+                ratio: value / sum,
+                label: label.clone().into(),
+                value: Some(*value),
+                ..Default::default()
+            })
+            .collect();
+        Ok(chart)
+    }
+
     /// Set the radius.
     pub fn set_radius(&mut self, radius: f64) {
         self.radius = radius;
     }
 
+    /// Set the inner radius, a non-zero inner radius turns the chart into a donut.
+    pub fn set_inner_radius(&mut self, inner_radius: f64) {
+        self.inner_radius = inner_radius;
+    }
+
+    /// Set a node to be drawn at the center of the chart, like a caption inside a donut's hole.
+    pub fn set_center_caption<T: Into<Box<dyn svg::Node>>>(&mut self, caption: T) {
+        self.center_caption = Some(caption.into());
+    }
+
     /// Normally, start is from 3 o clock clockwise.
     pub fn set_start(&mut self, offset: f64, style: StartStyle) {
         self.start_offset = offset;
         self.start_style = style;
     }
 
+    /// Set the total angle covered by the segments, like PI for a half circle gauge.
+    ///
+    /// This replaces an end set with [`PieChart::set_end`].
+    pub fn set_sweep(&mut self, sweep: f64) {
+        self.sweep = sweep.clamp(0.0, 2.0 * PI);
+        self.end = None;
+    }
+
+    /// Set the angle at which the last segment ends, the sweep runs clockwise from the start.
+    ///
+    /// The sweep follows the start offset when it changes later on. This replaces a sweep set
+    /// with [`PieChart::set_sweep`].
+    pub fn set_end(&mut self, end: f64) {
+        self.end = Some(end);
+    }
+
+    /// The total angle covered by the segments.
+    fn sweep(&self) -> f64 {
+        match self.end {
+            Some(end) => {
+                let sweep = (end - self.start_offset).rem_euclid(2.0 * PI);
+                // An end equal to the start is the full circle, not an empty chart.
+                if sweep == 0.0 {
+                    2.0 * PI
+                } else {
+                    sweep
+                }
+            }
+            None => self.sweep,
+        }
+    }
+
+    /// Set the palette used for segments without a color.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// Set a needle to be drawn on top of the segments.
+    pub fn set_needle(&mut self, needle: Needle) {
+        self.needle = Some(needle);
+    }
+
+    /// Set how small segments are merged into a single segment.
+    pub fn set_other(&mut self, other: OtherBucket) {
+        self.other = Some(other);
+    }
+
+    /// Set the distance segments are moved outward, for segments that don't specify their own.
+    pub fn set_explode(&mut self, distance: f64) {
+        self.explode = distance;
+    }
+
+    /// Set how the segment labels are formatted.
+    pub fn set_label_format(&mut self, format: LabelFormat) {
+        self.label_format = format;
+    }
+
+    /// Set the font size of the segment labels, used to determine whether they fit in a segment.
+    pub fn set_label_font_size(&mut self, font_size: f64) {
+        self.label_font_size = font_size;
+    }
+
+    /// Set an attribute on the segment labels.
+    pub fn set_label_attribute<T: Into<String>, U: Into<Value>>(&mut self, name: T, value: U) {
+        self.label_attributes.insert(name.into(), value.into());
+    }
+
+    /// Set the length of the leader lines of labels outside the chart, defaults to 15% of the radius.
+    pub fn set_leader_length(&mut self, length: f64) {
+        self.leader_length = Some(length);
+    }
+
     /// Set the segments in this chart.
     pub fn set_segments<T: Into<PieSegment> + Clone>(&mut self, segments: &[T]) {
         self.segments = segments
@@ -74,89 +636,591 @@ impl PieChart {
             .collect();
     }
 
+    /// Set the segments in this chart after validating them, ratios that do not sum to one are
+    /// handled according to the policy. On error the segments are left unchanged.
+    pub fn try_set_segments<T: Into<PieSegment> + Clone>(
+        &mut self,
+        segments: &[T],
+        policy: SumPolicy,
+    ) -> Result<(), PieChartError> {
+        let mut segments: Vec<PieSegment> = segments
+            .iter()
+            .map(|z| Into::<PieSegment>::into(z.clone()))
+            .collect();
+        let sum = validate(segments.iter().map(|s| s.ratio))?;
+        match policy {
+            SumPolicy::Normalize => {
+                for s in segments.iter_mut() {
+                    s.ratio /= sum;
+                }
+            }
+            SumPolicy::Gap if sum > 1.0 + SUM_TOLERANCE => {
+                return Err(PieChartError::InvalidSum(sum));
+            }
+            SumPolicy::Gap => {}
+            SumPolicy::Error if (sum - 1.0).abs() > SUM_TOLERANCE => {
+                return Err(PieChartError::InvalidSum(sum));
+            }
+            SumPolicy::Error => {}
+        }
+        self.segments = segments;
+        Ok(())
+    }
+
     /// Mutable retrieval of a segment.
     pub fn segment_mut(&mut self, index: usize) -> Option<&mut PieSegment> {
         self.segments.get_mut(index)
     }
 
-    /// Render the piechart to svg.
-    pub fn svg(&self) -> Group {
-        let mut group = Group::new();
+    /// The color of a segment, taking the palette into account.
+    fn segment_color(&self, index: usize) -> Color {
+        self.segments
+            .get(index)
+            .and_then(|s| s.color)
+            .unwrap_or_else(|| self.palette.color(index, self.segments.len()))
+    }
 
-        let mut current_pos: f64 = self.start_offset;
+    /// The other bucket and whether each segment is merged into it, none if nothing is merged.
+    fn merge_flags(&self) -> Option<(&OtherBucket, Vec<bool>)> {
+        let other = self.other.as_ref()?;
+        let mut by_size: Vec<usize> = (0..self.segments.len()).collect();
+        by_size.sort_by(|a, b| self.segments[*b].ratio.total_cmp(&self.segments[*a].ratio));
+        let mut merge = vec![false; self.segments.len()];
+        for (rank, index) in by_size.iter().enumerate() {
+            merge[*index] = other.merges(&self.segments[*index], rank);
+        }
+        // Merging a single segment would only hide its label.
+        if merge.iter().filter(|m| **m).count() < 2 {
+            return None;
+        }
+        Some((other, merge))
+    }
 
-        // If we have a centered style, subtract by half of the first ratio.
-        match self.start_style {
-            StartStyle::Edge => {}
-            StartStyle::Center => {
-                if let Some(s) = self.segments.first() {
-                    current_pos -= (s.ratio / 2.0) * 2.0 * PI;
-                }
+    /// This chart with the small segments merged, as it is drawn.
+    ///
+    /// Colors are resolved before merging, such that the remaining segments keep their colors.
+    fn merged(&self) -> Cow<'_, PieChart> {
+        let Some((other, merge)) = self.merge_flags() else {
+            return Cow::Borrowed(self);
+        };
+
+        let mut segments = vec![];
+        let mut bucket = PieSegment {
+            label: other.label.clone(),
+            color: Some(other.color),
+            value: Some(0.0),
+            ..Default::default()
+        };
+        for (si, s) in self.segments.iter().enumerate() {
+            if merge[si] {
+                bucket.ratio += s.ratio;
+                bucket.value = bucket.value.zip(s.value).map(|(a, b)| a + b);
+            } else {
+                segments.push(PieSegment {
+                    color: Some(self.segment_color(si)),
+                    ..s.clone()
+                });
             }
-            StartStyle::CenterLargest => {
-                // Approach:
-                // Assume all segments are equal.
-                // Align the largest part to its centered-equal direction.
-                let ideal = 1.0f64 / (self.segments.len() as f64);
-                // https://stackoverflow.com/a/53908709
-                let max_segment: Option<(usize, &PieSegment)> = self
-                    .segments
-                    .iter()
-                    .enumerate()
-                    .max_by(|(_, a), (_, b)| a.ratio.total_cmp(&b.ratio));
-                if let Some((max_index, max_segment)) = max_segment {
-                    // Calculate position of the center of the max segment in ideal position.
-                    let max_segment_ideal = max_index as f64 * ideal + ideal / 2.0;
-                    // Calculate the position of the center of the max segment in reality, accumulating ratios.
-                    let max_segment_real =
-                        (0..max_index).map(|i| self.segments[i].ratio).sum::<f64>()
-                            + max_segment.ratio / 2.0;
-                    // Ofset it by the difference.
-                    current_pos = (max_segment_ideal - max_segment_real) * 2.0 * PI;
-                }
+        }
+        segments.push(bucket);
+        Cow::Owned(PieChart {
+            segments,
+            other: None,
+            ..self.clone()
+        })
+    }
+
+    /// Create a legend with an entry for each provided label, using the segment's color.
+    ///
+    /// Label i belongs to segment i. Segments merged into the other bucket are left out, and a
+    /// single entry for the bucket is added at the end.
+    pub fn legend<T: Into<String> + Clone>(&self, labels: &[T]) -> Legend {
+        self.legend_fills(labels, None)
+    }
+
+    /// Create a legend like legend, using the segment's fill and adding the definitions it needs
+    /// to defs.
+    pub fn legend_with_defs<T: Into<String> + Clone>(
+        &self,
+        labels: &[T],
+        defs: &mut Defs,
+    ) -> Legend {
+        self.legend_fills(labels, Some(defs))
+    }
+
+    fn legend_fills<T: Into<String> + Clone>(
+        &self,
+        labels: &[T],
+        mut defs: Option<&mut Defs>,
+    ) -> Legend {
+        let merge = self.merge_flags();
+        let mut legend = Legend::new();
+        // Labels belong to the segments as provided, merged segments are left out.
+        for (si, label) in labels.iter().enumerate().take(self.segments.len()) {
+            if merge.as_ref().is_some_and(|(_, m)| m[si]) {
+                continue;
             }
+            let fill = match (&self.segments[si].fill, defs.as_deref_mut()) {
+                (Some(paint), Some(defs)) => paint.resolve(defs),
+                _ => self.segment_color(si).into(),
+            };
+            legend = legend.entry(LegendEntry::new(label.clone()).set("fill", fill));
+        }
+        if let Some((other, _)) = merge {
+            legend = legend.entry(LegendEntry::new(other.label.clone()).set("fill", other.color));
         }
+        legend
+    }
 
-        for (si, s) in self.segments.iter().enumerate() {
-            let angle = s.ratio * 2.0 * PI;
-            let arc_sx = (current_pos).cos() * self.radius;
-            let arc_sy = (current_pos).sin() * self.radius;
-            let arc_ex = (angle + current_pos).cos() * self.radius;
-            let arc_ey = (angle + current_pos).sin() * self.radius;
-            let data = Data::new()
-                .move_to((0.0, 0.0)) // all circles start in 0,0
-                .line_to((arc_sx, arc_sy))
-                .elliptical_arc_by((
-                    self.radius,
-                    self.radius,
-                    0.0, // x axis rotation of the ellipse
-                    0,
-                    1, // large flag arc, sweep flag
-                    arc_ex - arc_sx,
-                    arc_ey - arc_sy,
-                ))
-                .line_to((arc_ex, arc_ey))
-                .close();
+    /// The angle at which the first segment starts, taking the start style into account.
+    fn start_position(&self) -> f64 {
+        let ratios: Vec<f64> = self.segments.iter().map(|s| s.ratio).collect();
+        start_position(&ratios, self.start_offset, self.start_style, self.sweep())
+    }
 
-            current_pos += angle;
-            let color = if s.color.is_empty() {
-                FALLBACK_COLORS[si % FALLBACK_COLORS.len()].to_owned()
+    /// The start angle and the angle spanned by each segment as drawn, in radians, clockwise from
+    /// 3 o clock.
+    pub fn segment_angles(&self) -> Vec<(f64, f64)> {
+        self.merged().angles()
+    }
+
+    fn angles(&self) -> Vec<(f64, f64)> {
+        let mut current_pos = self.start_position();
+        self.segments
+            .iter()
+            .map(|s| {
+                let angle = s.ratio * self.sweep();
+                let start = current_pos;
+                current_pos += angle;
+                (start, angle)
+            })
+            .collect()
+    }
+
+    /// Offset of a segment from the center, along its bisector, if it is exploded.
+    fn segment_offset(&self, index: usize, start: f64, angle: f64) -> (f64, f64) {
+        let distance = self
+            .segments
+            .get(index)
+            .and_then(|s| s.explode)
+            .unwrap_or(self.explode);
+        // A full circle has no meaningful direction to move in.
+        if distance == 0.0 || angle >= 2.0 * PI - 1e-9 {
+            return (0.0, 0.0);
+        }
+        let mid = start + angle / 2.0;
+        (mid.cos() * distance, mid.sin() * distance)
+    }
+
+    /// The largest distance any segment is moved outward.
+    fn max_explode(&self) -> f64 {
+        self.segments
+            .iter()
+            .map(|s| s.explode.unwrap_or(self.explode))
+            .fold(0.0, f64::max)
+    }
+
+    /// Estimated width of a label, from the number of characters.
+    fn label_width(&self, text: &str) -> f64 {
+        text.chars().count() as f64 * self.label_font_size * 0.6
+    }
+
+    /// Whether a label box centered at the provided position lies entirely within the segment.
+    fn label_fits(&self, text: &str, center: (f64, f64), start: f64, angle: f64) -> bool {
+        let hw = self.label_width(text) / 2.0;
+        let hh = self.label_font_size / 2.0;
+        [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)]
+            .iter()
+            .all(|(dx, dy)| {
+                let (x, y) = (center.0 + dx, center.1 + dy);
+                let r = (x * x + y * y).sqrt();
+                let within_angle =
+                    angle >= 2.0 * PI || (y.atan2(x) - start).rem_euclid(2.0 * PI) <= angle;
+                r <= self.radius && r >= self.inner_radius && within_angle
+            })
+    }
+
+    fn label_text(&self, text: String, x: f64, y: f64, anchor: &str) -> Text {
+        let mut text = Text::new(text)
+            .set("x", x)
+            .set("y", y)
+            .set("font-size", self.label_font_size)
+            .set("text-anchor", anchor)
+            .set("dominant-baseline", "middle");
+        let attr = text.get_attributes_mut().unwrap();
+        for (k, v) in self.label_attributes.iter() {
+            attr.insert(k.clone(), v.clone());
+        }
+        text
+    }
+
+    /// Create the segment labels, inside the segments if they fit and outside with leader lines
+    /// otherwise.
+    fn labels_svg(&self) -> Group {
+        let mut group = Group::new();
+        let leader = self.leader_length.unwrap_or(self.radius * 0.15);
+        let line_height = self.label_font_size * 1.2;
+        // Labels inside segments are placed halfway the ring, or a bit further out for pies.
+        let inside_radius = if self.inner_radius > 0.0 {
+            (self.inner_radius + self.radius) / 2.0
+        } else {
+            self.radius * 0.65
+        };
+
+        let mut right: Vec<OutsideLabel> = vec![];
+        let mut left: Vec<OutsideLabel> = vec![];
+        for (si, (s, (start, angle))) in self.segments.iter().zip(self.angles()).enumerate() {
+            let text = self.label_format.format(s);
+            if text.is_empty() {
+                continue;
+            }
+            let mid = start + angle / 2.0;
+            let offset = self.segment_offset(si, start, angle);
+            let center = (mid.cos() * inside_radius, mid.sin() * inside_radius);
+            if self.label_fits(&text, center, start, angle) {
+                let (x, y) = (center.0 + offset.0, center.1 + offset.1);
+                let mut text = self.label_text(text, x, y, "middle");
+                // Unless a fill is given, labels on segments pick whatever is readable on them.
+                if !self.label_attributes.contains_key("fill") {
+                    text.assign("fill", self.segment_color(si).contrasting());
+                }
+                group.append(text);
+                continue;
+            }
+            let label = OutsideLabel {
+                index: si,
+                text,
+                angle: mid,
+                offset,
+                y: mid.sin() * (self.radius + leader),
+            };
+            if mid.cos() >= 0.0 {
+                right.push(label);
             } else {
-                s.color.clone()
+                left.push(label);
+            }
+        }
+
+        // Space the outside labels on each side, such that they do not overlap.
+        let bound = self.radius + self.max_explode() + leader;
+        for (labels, side) in [(&mut right, 1.0), (&mut left, -1.0)] {
+            labels.sort_by(|a, b| a.y.total_cmp(&b.y));
+            // Push labels down where they overlap their predecessor.
+            for i in 1..labels.len() {
+                labels[i].y = labels[i].y.max(labels[i - 1].y + line_height);
+            }
+            // And back up where that pushed them beyond the bottom of the chart.
+            if let Some(last) = labels.last_mut() {
+                last.y = last.y.min(bound);
+            }
+            for i in (0..labels.len().saturating_sub(1)).rev() {
+                labels[i].y = labels[i].y.min(labels[i + 1].y - line_height);
+            }
+
+            for label in labels.iter() {
+                let anchor = (
+                    label.angle.cos() * self.radius + label.offset.0,
+                    label.angle.sin() * self.radius + label.offset.1,
+                );
+                let elbow = (label.angle.cos() * bound, label.y);
+                let end = (side * (bound + leader), label.y);
+                let points = format!(
+                    "{},{} {},{} {},{}",
+                    anchor.0, anchor.1, elbow.0, elbow.1, end.0, end.1
+                );
+                group.append(
+                    Polyline::new()
+                        .set("points", points)
+                        .set("fill", "none")
+                        .set("stroke", self.segment_color(label.index)),
+                );
+                let text_anchor = if side > 0.0 { "start" } else { "end" };
+                let x = end.0 + side * self.label_font_size * 0.3;
+                group.append(self.label_text(label.text.clone(), x, label.y, text_anchor));
+            }
+        }
+        group
+    }
+
+    /// Render the piechart to svg.
+    ///
+    /// Definitions needed by the segment fills are included in the group.
+    pub fn svg(&self) -> Group {
+        let mut defs = Defs::new();
+        let chart = self.svg_with_defs(&mut defs);
+        if defs.is_empty() {
+            return chart;
+        }
+        Group::new().add(defs).add(chart)
+    }
+
+    /// Render the piechart to svg, adding the definitions needed by the segment fills to defs.
+    pub fn svg_with_defs(&self, defs: &mut Defs) -> Group {
+        self.merged().render(defs)
+    }
+
+    fn render(&self, defs: &mut Defs) -> Group {
+        let mut group = Group::new();
+
+        for (si, (start, angle)) in self.angles().into_iter().enumerate() {
+            let offset = self.segment_offset(si, start, angle);
+            let data = sector_data(offset, self.inner_radius, self.radius, start, angle);
+
+            let fill = match self.segments.get(si).and_then(|s| s.fill.as_ref()) {
+                Some(paint) => paint.resolve(defs),
+                None => self.segment_color(si).into(),
             };
             let path = Path::new()
-                .set("fill", color)
+                .set("fill", fill)
                 // .set("stroke", "none")
                 // .set("stroke-width", 3)
                 .set("d", data);
             group = group.add(path);
         }
+        let labels = self.labels_svg();
+        if labels.get_children().is_some_and(|c| !c.is_empty()) {
+            group = group.add(labels);
+        }
+        if let Some(needle) = &self.needle {
+            let angle = self.start_position() + needle.position * self.sweep();
+            group = group.add(needle.svg(angle, needle.length.unwrap_or(self.radius), defs));
+        }
+        if let Some(caption) = &self.center_caption {
+            group = group.add(caption.clone());
+        }
         group
     }
 }
 
-impl From<PieChart> for Box<(dyn svg::Node + 'static)> {
+impl From<PieChart> for Box<dyn svg::Node + 'static> {
     fn from(val: PieChart) -> Self {
         Box::new(val.svg())
     }
 }
+
+/// A node in a sunburst chart, its children are drawn in the next ring, nested inside its span.
+#[derive(Debug, Clone, Default)]
+pub struct SunburstNode {
+    /// The value of this node, children divide the span of their parent by their values.
+    ///
+    /// If the children sum to less than this value, the remainder of the span is left empty.
+    pub value: f64,
+    pub label: String,
+    /// Color for this node, if none the parent's color is used.
+    pub color: Option<Color>,
+    pub children: Vec<SunburstNode>,
+}
+
+impl SunburstNode {
+    /// Create a node without children.
+    pub fn new<T: Into<String>>(label: T, value: f64) -> Self {
+        SunburstNode {
+            value,
+            label: label.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a node with the child appended.
+    pub fn child(mut self, child: SunburstNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Returns a node with the color set to the provided value.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// The value the span of this node is divided by, children may exceed the node's own value.
+    fn total(&self) -> f64 {
+        self.value
+            .max(self.children.iter().map(|c| c.value.max(0.0)).sum())
+    }
+
+    /// Number of rings needed to draw this node and its descendants.
+    fn depth(&self) -> usize {
+        1 + self.children.iter().map(|c| c.depth()).max().unwrap_or(0)
+    }
+}
+
+/// A single sector of a sunburst chart.
+struct SunburstSpan {
+    depth: usize,
+    start: f64,
+    angle: f64,
+    color: Color,
+}
+
+/// A hierarchical pie chart, each ring is a level of the tree, starting from the center.
+#[derive(Debug, Clone)]
+pub struct Sunburst {
+    roots: Vec<SunburstNode>,
+    radius: f64,
+    inner_radius: f64,
+    start_offset: f64,
+    start_style: StartStyle,
+    attributes: Attributes,
+    palette: Palette,
+}
+
+impl Default for Sunburst {
+    fn default() -> Self {
+        Self {
+            roots: vec![],
+            radius: 1.0,
+            inner_radius: 0.0,
+            start_offset: 0.0,
+            start_style: StartStyle::Edge,
+            attributes: Default::default(),
+            palette: Default::default(),
+        }
+    }
+}
+
+impl Sunburst {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the outer radius of the outermost ring.
+    pub fn set_radius(&mut self, radius: f64) {
+        self.radius = radius;
+    }
+
+    /// Set the inner radius of the innermost ring, the rings divide the remainder equally.
+    pub fn set_inner_radius(&mut self, inner_radius: f64) {
+        self.inner_radius = inner_radius;
+    }
+
+    /// Normally, start is from 3 o clock clockwise, the style applies to the innermost ring.
+    pub fn set_start(&mut self, offset: f64, style: StartStyle) {
+        self.start_offset = offset;
+        self.start_style = style;
+    }
+
+    /// Set an attribute on every sector, like a stroke to separate nodes that share a color.
+    pub fn set_attribute<T: Into<String>, U: Into<Value>>(&mut self, name: T, value: U) {
+        self.attributes.insert(name.into(), value.into());
+    }
+
+    /// Set the palette used for nodes in the innermost ring without a color.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// Add a node to the innermost ring.
+    pub fn add_root(&mut self, node: SunburstNode) {
+        self.roots.push(node);
+    }
+
+    /// Create a legend with an entry for each node in the innermost ring.
+    pub fn legend(&self) -> Legend {
+        let mut legend = Legend::new();
+        for (ri, root) in self.roots.iter().enumerate() {
+            legend =
+                legend.entry(LegendEntry::new(root.label.clone()).set("fill", self.root_color(ri)));
+        }
+        legend
+    }
+
+    fn root_color(&self, index: usize) -> Color {
+        self.roots
+            .get(index)
+            .and_then(|r| r.color)
+            .unwrap_or_else(|| self.palette.color(index, self.roots.len()))
+    }
+
+    /// Collect the spans of the node and its descendants.
+    fn node_spans(
+        node: &SunburstNode,
+        depth: usize,
+        start: f64,
+        angle: f64,
+        color: Color,
+        spans: &mut Vec<SunburstSpan>,
+    ) {
+        let total = node.total();
+        let mut current_pos = start;
+        for child in node.children.iter() {
+            let child_angle = if total > 0.0 {
+                angle * child.value.max(0.0) / total
+            } else {
+                0.0
+            };
+            let child_color = child.color.unwrap_or(color);
+            Self::node_spans(
+                child,
+                depth + 1,
+                current_pos,
+                child_angle,
+                child_color,
+                spans,
+            );
+            current_pos += child_angle;
+        }
+        spans.push(SunburstSpan {
+            depth,
+            start,
+            angle,
+            color,
+        });
+    }
+
+    /// The spans of all nodes, parents before their children.
+    fn spans(&self) -> Vec<SunburstSpan> {
+        let total: f64 = self.roots.iter().map(|r| r.value.max(0.0)).sum();
+        if total <= 0.0 {
+            return vec![];
+        }
+        let ratios: Vec<f64> = self
+            .roots
+            .iter()
+            .map(|r| r.value.max(0.0) / total)
+            .collect();
+        let mut current_pos =
+            start_position(&ratios, self.start_offset, self.start_style, 2.0 * PI);
+        let mut spans = vec![];
+        for (ri, (root, ratio)) in self.roots.iter().zip(ratios).enumerate() {
+            let angle = ratio * 2.0 * PI;
+            Self::node_spans(root, 0, current_pos, angle, self.root_color(ri), &mut spans);
+            current_pos += angle;
+        }
+        spans.sort_by_key(|s| s.depth);
+        spans
+    }
+
+    /// Render the sunburst to svg.
+    pub fn svg(&self) -> Group {
+        let mut group = Group::new();
+        let depth = self.roots.iter().map(|r| r.depth()).max().unwrap_or(1);
+        let ring_width = (self.radius - self.inner_radius) / depth as f64;
+        for span in self.spans() {
+            if span.angle <= 0.0 {
+                continue;
+            }
+            let inner = self.inner_radius + span.depth as f64 * ring_width;
+            let data = sector_data(
+                (0.0, 0.0),
+                inner,
+                inner + ring_width,
+                span.start,
+                span.angle,
+            );
+            let mut path = Path::new().set("fill", span.color).set("d", data);
+            let attr = path.get_attributes_mut().unwrap();
+            for (k, v) in self.attributes.iter() {
+                attr.insert(k.clone(), v.clone());
+            }
+            group = group.add(path);
+        }
+        group
+    }
+}
+
+impl From<Sunburst> for Box<dyn svg::Node + 'static> {
+    fn from(val: Sunburst) -> Self {
+        Box::new(val.svg())
+    }
+}
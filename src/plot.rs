@@ -1,6 +1,9 @@
+use crate::defs::{Defs, Paint};
+use crate::legend::{Legend, LegendEntry, Swatch};
+use crate::palette::Palette;
 use crate::transform::*;
 use std::rc::Rc;
-use svg::node::element::{Group, Polyline};
+use svg::node::element::{path::Data, Circle, Group, Path, Polyline, Rectangle, Text};
 use svg::node::{Attributes, Node, Value};
 
 #[derive(Debug, Copy, Clone, Default)]
@@ -16,12 +19,378 @@ enum AxisOrientation {
     Vertical,
 }
 
+/// Determines where the major ticks of an axis are placed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ticks {
+    /// No ticks at all.
+    None,
+    /// Nice positions are picked automatically, aiming for approximately this many major ticks.
+    Auto(usize),
+    /// Major ticks at the provided positions, labels are formatted automatically.
+    Positions(Vec<f64>),
+    /// Major ticks at the provided positions, with the provided labels.
+    Labeled(Vec<(f64, String)>),
+}
+
+impl Default for Ticks {
+    fn default() -> Self {
+        Ticks::Auto(5)
+    }
+}
+
+/// Round a value to a nice number; 1, 2 or 5 times a power of ten.
+///
+/// If round is false the returned number is the nice number equal or larger than the value.
+fn nice_number(v: f64, round: bool) -> f64 {
+    let exponent = v.log10().floor();
+    let fraction = v / 10f64.powf(exponent);
+    let nice = if round {
+        if fraction < 1.5 {
+            1.0
+        } else if fraction < 3.0 {
+            2.0
+        } else if fraction < 7.0 {
+            5.0
+        } else {
+            10.0
+        }
+    } else if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * 10f64.powf(exponent)
+}
+
+/// Calculate nice tick positions between min and max, aiming for approximately count ticks.
+///
+/// Returns the positions that lie within the range and the step between them.
+pub fn nice_ticks(min: f64, max: f64, count: usize) -> (Vec<f64>, f64) {
+    let (lower, upper) = (min.min(max), min.max(max));
+    let span = upper - lower;
+    if !span.is_finite() || span <= 0.0 || count == 0 {
+        return (vec![], 0.0);
+    }
+    let step = nice_number(nice_number(span, false) / (count.max(2) - 1) as f64, true);
+    // Small tolerance to keep ticks that are on the range's edges despite rounding errors.
+    let eps = step * 1e-9;
+    let first = (lower / step).ceil() as i64;
+    let last = (upper / step).floor() as i64;
+    let mut positions = vec![];
+    for i in first..=last {
+        let v = i as f64 * step;
+        if v >= lower - eps && v <= upper + eps {
+            positions.push(v);
+        }
+    }
+    (positions, step)
+}
+
+/// Format a tick value, using as many decimals as the step between ticks requires.
+pub fn format_tick(v: f64, step: f64) -> String {
+    let decimals = if step > 0.0 {
+        (-step.log10().floor()).max(0.0) as usize
+    } else {
+        0
+    };
+    let s = format!("{:.*}", decimals, v);
+    // Prevent '-0' and '-0.0' labels.
+    if s.trim_start_matches('-')
+        .chars()
+        .all(|c| c == '0' || c == '.')
+    {
+        s.trim_start_matches('-').to_owned()
+    } else {
+        s
+    }
+}
+
+/// Upper bound on the number of minor ticks on an axis, tiny spacing would otherwise produce an
+/// unbounded number of them.
+const MAX_MINOR_TICKS: usize = 1000;
+
+/// Format tick values with a shared number of decimals, the fewest that represent every value.
+///
+/// Unlike [`format_tick`] this does not assume the values are evenly spaced.
+pub fn format_ticks(values: &[f64]) -> Vec<String> {
+    const MAX_DECIMALS: usize = 12;
+    let represents = |v: f64, decimals: usize| {
+        let rounded: f64 = format!("{:.*}", decimals, v).parse().unwrap_or(f64::NAN);
+        (rounded - v).abs() <= 1e-9 * v.abs().max(1.0)
+    };
+    let decimals = (0..MAX_DECIMALS)
+        .find(|d| values.iter().all(|v| represents(*v, *d)))
+        .unwrap_or(MAX_DECIMALS);
+    values
+        .iter()
+        .map(|v| format_tick(*v, 10f64.powi(-(decimals as i32))))
+        .collect()
+}
+
+/// Format the ticks at these powers of the base on a logarithmic scale.
+///
+/// Powers are written out if all of them are short, otherwise all use exponents, such that the
+/// labels of an axis share one format.
+fn format_powers(base: f64, exponents: &[i32]) -> Vec<String> {
+    let written_out = exponents
+        .iter()
+        .all(|e| (0..5).contains(e) || (base == 10.0 && (-3..0).contains(e)));
+    exponents
+        .iter()
+        .map(|e| {
+            let v = base.powi(*e);
+            if written_out {
+                format_tick(v, v.abs())
+            } else {
+                format!("{}^{}", base, e)
+            }
+        })
+        .collect()
+}
+
+/// A mapping from plot values to a linear space in which values are spaced evenly on the canvas.
+pub trait ScaleTransform: std::fmt::Debug {
+    /// Map a plot value to the linear space.
+    fn forward(&self, v: f64) -> f64;
+
+    /// Map a value in the linear space back to a plot value.
+    fn inverse(&self, v: f64) -> f64;
+
+    /// Major tick positions and labels between min and max, aiming for approximately count ticks.
+    ///
+    /// By default nice ticks are calculated in the linear space and mapped back to plot values.
+    fn ticks(&self, min: f64, max: f64, count: usize) -> Vec<(f64, String)> {
+        let (positions, step) = nice_ticks(self.forward(min), self.forward(max), count);
+        positions
+            .iter()
+            .map(|t| {
+                let v = self.inverse(*t);
+                // Estimate the precision from the distance to the next tick in plot values.
+                let next = self.inverse(*t + step);
+                (v, format_tick(v, (next - v).abs()))
+            })
+            .collect()
+    }
+}
+
+/// A scale transform made from a pair of closures.
+struct FnScale<F, G> {
+    forward: F,
+    inverse: G,
+}
+
+impl<F, G> std::fmt::Debug for FnScale<F, G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FnScale")
+    }
+}
+
+impl<F, G> ScaleTransform for FnScale<F, G>
+where
+    F: Fn(f64) -> f64,
+    G: Fn(f64) -> f64,
+{
+    fn forward(&self, v: f64) -> f64 {
+        (self.forward)(v)
+    }
+    fn inverse(&self, v: f64) -> f64 {
+        (self.inverse)(v)
+    }
+}
+
+/// The scale of an axis, determines how plot values are distributed over the canvas.
 #[derive(Debug, Clone, Default)]
+pub enum Scale {
+    /// Values are spaced linearly.
+    #[default]
+    Linear,
+    /// Logarithmic scale with the provided base, only positive values can be shown.
+    Log(f64),
+    /// Symmetric logarithmic scale, approximately linear within the threshold around zero and
+    /// logarithmic beyond it. Allows showing negative values and zero.
+    SymLog { base: f64, threshold: f64 },
+    /// A user provided mapping.
+    Custom(Rc<dyn ScaleTransform>),
+}
+
+impl Scale {
+    /// Logarithmic scale in base 10.
+    pub fn log10() -> Self {
+        Scale::Log(10.0)
+    }
+
+    /// Logarithmic scale in base 2.
+    pub fn log2() -> Self {
+        Scale::Log(2.0)
+    }
+
+    /// Symmetric logarithmic scale in base 10, linear within the threshold around zero.
+    pub fn symlog(threshold: f64) -> Self {
+        Scale::SymLog {
+            base: 10.0,
+            threshold,
+        }
+    }
+
+    /// Scale from a forward and inverse closure.
+    pub fn custom<F, G>(forward: F, inverse: G) -> Self
+    where
+        F: Fn(f64) -> f64 + 'static,
+        G: Fn(f64) -> f64 + 'static,
+    {
+        Scale::Custom(Rc::new(FnScale { forward, inverse }))
+    }
+
+    /// Map a plot value to the linear space of this scale.
+    pub fn forward(&self, v: f64) -> f64 {
+        match self {
+            Scale::Linear => v,
+            Scale::Log(base) => v.log(*base),
+            Scale::SymLog { base, threshold } => {
+                v.signum() * (1.0 + v.abs() / threshold).log(*base)
+            }
+            Scale::Custom(t) => t.forward(v),
+        }
+    }
+
+    /// Map a value in the linear space of this scale back to a plot value.
+    pub fn inverse(&self, v: f64) -> f64 {
+        match self {
+            Scale::Linear => v,
+            Scale::Log(base) => base.powf(v),
+            Scale::SymLog { base, threshold } => {
+                v.signum() * threshold * (base.powf(v.abs()) - 1.0)
+            }
+            Scale::Custom(t) => t.inverse(v),
+        }
+    }
+
+    /// The integer exponents of the base that fall within the range, thinned out to approximately
+    /// count values. Returns None if there are too few powers in the range to be useful.
+    fn powers(base: f64, lower: f64, upper: f64, count: usize) -> Option<Vec<i32>> {
+        // Tolerance to keep exact powers despite rounding errors in the logarithm.
+        let first = (lower.log(base) - 1e-9).ceil() as i32;
+        let last = (upper.log(base) + 1e-9).floor() as i32;
+        if last - first < 1 {
+            return None;
+        }
+        let every = ((last - first + 1) as usize)
+            .div_ceil(count.max(2) + 1)
+            .max(1);
+        Some((first..=last).step_by(every).collect())
+    }
+
+    /// Major tick positions and labels between min and max, aiming for approximately count ticks.
+    pub fn ticks(&self, min: f64, max: f64, count: usize) -> Vec<(f64, String)> {
+        let (lower, upper) = (min.min(max), min.max(max));
+        match self {
+            Scale::Linear => {
+                let (positions, step) = nice_ticks(lower, upper, count);
+                positions
+                    .iter()
+                    .map(|v| (*v, format_tick(*v, step)))
+                    .collect()
+            }
+            Scale::Log(base) => match Self::powers(*base, lower, upper, count) {
+                Some(powers) => powers
+                    .iter()
+                    .map(|e| base.powi(*e))
+                    .zip(format_powers(*base, &powers))
+                    .collect(),
+                // Less than a single order of magnitude, nice linear ticks work better.
+                None => Scale::Linear.ticks(lower, upper, count),
+            },
+            Scale::SymLog { base, threshold } => {
+                let mut ticks = vec![];
+                if lower <= 0.0 && upper >= 0.0 {
+                    ticks.push((0.0, "0".to_owned()));
+                }
+                // Powers above the threshold on either side of zero.
+                let first = (threshold.log(*base) - 1e-9).ceil() as i32;
+                let largest = lower.abs().max(upper.abs());
+                let last = (largest.log(*base) + 1e-9).floor() as i32;
+                if last >= first {
+                    let every = ((last - first + 1) as usize)
+                        .div_ceil(count.max(2) + 1)
+                        .max(1);
+                    let powers: Vec<i32> = (first..=last).step_by(every).collect();
+                    for (e, label) in powers.iter().zip(format_powers(*base, &powers)) {
+                        let v = base.powi(*e);
+                        for (signed, label) in [(v, label.clone()), (-v, format!("-{}", label))] {
+                            if signed >= lower && signed <= upper {
+                                ticks.push((signed, label));
+                            }
+                        }
+                    }
+                }
+                ticks
+            }
+            Scale::Custom(t) => t.ticks(min, max, count),
+        }
+    }
+
+    /// Minor ticks at the integer multiples of each power of the base, for logarithmic scales.
+    fn log_minor_ticks(&self, lower: f64, upper: f64) -> Option<Vec<f64>> {
+        let base = match self {
+            Scale::Log(base) | Scale::SymLog { base, .. } => *base,
+            _ => return None,
+        };
+        let largest = lower.abs().max(upper.abs());
+        let smallest = match self {
+            Scale::SymLog { threshold, .. } => *threshold,
+            _ => lower,
+        };
+        if base.fract() != 0.0 || base < 3.0 || smallest <= 0.0 {
+            return None;
+        }
+        let mut minor = vec![];
+        for e in (smallest.log(base).floor() as i32)..=(largest.log(base).ceil() as i32) {
+            for m in 2..(base as i32) {
+                let v = m as f64 * base.powi(e);
+                for signed in [v, -v] {
+                    if signed >= lower && signed <= upper {
+                        minor.push(signed);
+                    }
+                }
+            }
+        }
+        Some(minor)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Axis {
     canvas_range: Range,
     plot_range: Range,
     orientation: AxisOrientation,
     attributes: Attributes,
+    scale: Scale,
+    ticks: Ticks,
+    minor_ticks: Option<usize>,
+    major_tick_length: f64,
+    minor_tick_length: f64,
+    tick_label_attributes: Attributes,
+}
+
+impl Default for Axis {
+    fn default() -> Self {
+        Self {
+            canvas_range: Default::default(),
+            plot_range: Default::default(),
+            orientation: Default::default(),
+            attributes: Default::default(),
+            scale: Default::default(),
+            ticks: Default::default(),
+            minor_ticks: None,
+            major_tick_length: 5.0,
+            minor_tick_length: 2.5,
+            tick_label_attributes: Default::default(),
+        }
+    }
 }
 
 impl Axis {
@@ -55,11 +424,178 @@ impl Axis {
         }
     }
 
+    /// The plot range used for projection, a logarithmic scale replaces bounds that are not
+    /// positive by a bound three powers of the base below the other bound.
+    fn range(&self) -> Range {
+        let Range { min, max } = self.plot_range;
+        match self.scale {
+            Scale::Log(base) if min <= 0.0 || max <= 0.0 => {
+                let positive = if min > 0.0 { min } else { max };
+                if positive <= 0.0 {
+                    Range {
+                        min: 1.0,
+                        max: base,
+                    }
+                } else if min <= 0.0 {
+                    Range {
+                        min: positive / base.powi(3),
+                        max,
+                    }
+                } else {
+                    Range {
+                        min,
+                        max: positive / base.powi(3),
+                    }
+                }
+            }
+            _ => self.plot_range,
+        }
+    }
+
     fn project(&self, v: f64) -> f64 {
-        let shifted = v - self.plot_range.min;
-        let ratio = shifted / (self.plot_range.max - self.plot_range.min);
+        let range = self.range();
+        let v = match self.scale {
+            // Values that a logarithm cannot map end up at the edge, such as zero.
+            Scale::Log(_) if v <= 0.0 => range.min.min(range.max),
+            _ => v,
+        };
+        let min = self.scale.forward(range.min);
+        let max = self.scale.forward(range.max);
+        let shifted = self.scale.forward(v) - min;
+        let ratio = shifted / (max - min);
         let canvas_pos = ratio * (self.canvas_range.max - self.canvas_range.min);
-        canvas_pos + self.canvas_range.min
+        match self.orientation {
+            AxisOrientation::Horizontal => canvas_pos + self.canvas_range.min,
+            // Svg's y axis points down, larger values must end up at the top of the canvas.
+            AxisOrientation::Vertical => self.canvas_range.max - canvas_pos,
+        }
+    }
+
+    /// The inverse of project, maps a canvas position back to a plot value.
+    fn unproject(&self, p: f64) -> f64 {
+        let range = self.range();
+        let canvas_pos = match self.orientation {
+            AxisOrientation::Horizontal => p - self.canvas_range.min,
+            AxisOrientation::Vertical => self.canvas_range.max - p,
+        };
+        let ratio = canvas_pos / (self.canvas_range.max - self.canvas_range.min);
+        let min = self.scale.forward(range.min);
+        let max = self.scale.forward(range.max);
+        self.scale.inverse(min + ratio * (max - min))
+    }
+
+    /// Canvas position of the data origin, clamped to the plot range, this is where the other
+    /// axis crosses this one.
+    fn origin(&self) -> f64 {
+        let range = self.range();
+        let lower = range.min.min(range.max);
+        let upper = range.min.max(range.max);
+        self.project(0.0f64.clamp(lower, upper))
+    }
+
+    fn in_plot_range(&self, v: f64) -> bool {
+        let range = self.range();
+        let lower = range.min.min(range.max);
+        let upper = range.min.max(range.max);
+        let eps = (upper - lower) * 1e-9;
+        v >= lower - eps && v <= upper + eps
+    }
+
+    /// Returns the major ticks with their labels and the minor tick positions.
+    fn tick_positions(&self) -> (Vec<(f64, String)>, Vec<f64>) {
+        let range = self.range();
+        let (major, step) = match &self.ticks {
+            Ticks::None => return (vec![], vec![]),
+            Ticks::Auto(count) => {
+                let major = self.scale.ticks(range.min, range.max, *count);
+                // Linear ticks are evenly spaced, their step determines the minor subdivisions.
+                let step = match self.scale {
+                    Scale::Linear if major.len() > 1 => Some((major[1].0 - major[0].0).abs()),
+                    _ => None,
+                };
+                (major, step)
+            }
+            Ticks::Positions(positions) => {
+                let mut positions = positions.clone();
+                positions.sort_by(f64::total_cmp);
+                positions.dedup();
+                let labels = format_ticks(&positions);
+                (positions.into_iter().zip(labels).collect(), None)
+            }
+            Ticks::Labeled(labeled) => (labeled.clone(), None),
+        };
+        let major: Vec<(f64, String)> = major
+            .into_iter()
+            .filter(|(v, _)| self.in_plot_range(*v))
+            .collect();
+
+        // Logarithmic scales place their minor ticks at the multiples of the powers.
+        if self.minor_ticks.is_none() {
+            let lower = range.min.min(range.max);
+            let upper = range.min.max(range.max);
+            if let Some(minor) = self.scale.log_minor_ticks(lower, upper) {
+                return (major, minor);
+            }
+        }
+
+        // Automatic minor ticks only for automatic major ticks, subdividing nicely.
+        let subdivisions = match (self.minor_ticks, step) {
+            (Some(n), _) => n,
+            (None, Some(step)) => {
+                let mantissa = step / 10f64.powf(step.log10().floor());
+                if (mantissa - 2.0).abs() < 0.5 {
+                    4
+                } else {
+                    5
+                }
+            }
+            (None, None) => 0,
+        };
+
+        // Subdivide in the linear space of the scale, such that minor ticks are evenly spaced.
+        let mut minor = vec![];
+        if subdivisions > 1 && major.len() > 1 {
+            let mut sorted: Vec<f64> = major.iter().map(|(v, _)| self.scale.forward(*v)).collect();
+            sorted.sort_by(f64::total_cmp);
+            sorted.dedup();
+            for w in sorted.windows(2) {
+                let minor_step = (w[1] - w[0]) / subdivisions as f64;
+                for i in 1..subdivisions.min(MAX_MINOR_TICKS) {
+                    minor.push(self.scale.inverse(w[0] + i as f64 * minor_step));
+                }
+            }
+            // Extend beyond the outer major ticks if there is room within the plot range.
+            if sorted.len() > 1 {
+                let last = sorted.len() - 1;
+                let before = (sorted[0], -(sorted[1] - sorted[0]) / subdivisions as f64);
+                let after = (
+                    sorted[last],
+                    (sorted[last] - sorted[last - 1]) / subdivisions as f64,
+                );
+                for (start, minor_step) in [before, after] {
+                    if !minor_step.is_finite() || minor_step == 0.0 {
+                        continue;
+                    }
+                    let mut t = start + minor_step;
+                    while minor.len() < MAX_MINOR_TICKS && self.in_plot_range(self.scale.inverse(t))
+                    {
+                        minor.push(self.scale.inverse(t));
+                        t += minor_step;
+                    }
+                }
+            }
+        }
+        minor.truncate(MAX_MINOR_TICKS);
+        (major, minor)
+    }
+
+    /// Add a tick mark at canvas position p with the provided length to the data.
+    fn tick_mark(&self, data: Data, p: f64, length: f64) -> Data {
+        // Ticks point away from the plot area; down for horizontal, left for vertical axes.
+        match self.orientation {
+            AxisOrientation::Horizontal => data.move_to((p, 0.0)).line_to((p, length)),
+            AxisOrientation::Vertical => data.move_to((0.0, p)).line_to((-length, p)),
+        }
     }
 
     fn svg(&self) -> Group {
@@ -74,8 +610,6 @@ impl Axis {
             }
         };
         for (x, y) in coords {
-            // let px = self.project(x);
-            // let py = self.project(y);
             points += &format!("{},{} ", x, y);
         }
         let mut path = Polyline::new().set("points", points);
@@ -84,9 +618,55 @@ impl Axis {
             attr.insert(k.clone(), v.clone());
         }
         group.append(path);
+
+        let (major, minor) = self.tick_positions();
+        if major.is_empty() {
+            return group;
+        }
+
+        let mut data = Data::new();
+        for (v, _) in major.iter() {
+            data = self.tick_mark(data, self.project(*v), self.major_tick_length);
+        }
+        for v in minor.iter() {
+            data = self.tick_mark(data, self.project(*v), self.minor_tick_length);
+        }
+        let mut ticks = Path::new().set("d", data);
+        let attr = ticks.get_attributes_mut().unwrap();
+        for (k, v) in self.attributes.iter() {
+            attr.insert(k.clone(), v.clone());
+        }
+        group.append(ticks);
+
+        let offset = self.major_tick_length * 1.5;
+        for (v, label) in major {
+            let p = self.project(v);
+            let text = match self.orientation {
+                AxisOrientation::Horizontal => Text::new(label)
+                    .set("x", p)
+                    .set("y", offset)
+                    .set("text-anchor", "middle")
+                    .set("dominant-baseline", "hanging"),
+                AxisOrientation::Vertical => Text::new(label)
+                    .set("x", -offset)
+                    .set("y", p)
+                    .set("text-anchor", "end")
+                    .set("dominant-baseline", "middle"),
+            };
+            let mut text = text;
+            let attr = text.get_attributes_mut().unwrap();
+            for (k, v) in self.tick_label_attributes.iter() {
+                attr.insert(k.clone(), v.clone());
+            }
+            group.append(text);
+        }
         group
     }
 
+    /// Set the range of plot values shown on the axis.
+    ///
+    /// On a logarithmic scale a bound that is not positive is replaced by a bound three powers of
+    /// the base below the other bound, values that are not positive are drawn at the lower edge.
     pub fn set_plot_range(&mut self, min: f64, max: f64) {
         self.plot_range.min = min;
         self.plot_range.max = max;
@@ -95,9 +675,61 @@ impl Axis {
         self.canvas_range.min = min;
         self.canvas_range.max = max;
     }
+    /// Make this a categorical axis, category i is centered on value i with a labeled tick.
+    pub fn set_categories<T: Into<String> + Clone>(&mut self, categories: &[T]) {
+        self.set_plot_range(-0.5, categories.len() as f64 - 0.5);
+        self.scale = Scale::Linear;
+        self.ticks = Ticks::Labeled(
+            categories
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (i as f64, c.clone().into()))
+                .collect(),
+        );
+        self.minor_ticks = Some(0);
+    }
+    /// Set the scale, which determines how plot values are distributed over the canvas.
+    ///
+    /// A logarithm base that is not larger than one is replaced by 10, as is a symmetric
+    /// logarithmic threshold that is not positive by 1. See [`Axis::set_plot_range`] for ranges
+    /// that include values a logarithmic scale cannot show.
+    pub fn set_scale(&mut self, scale: Scale) {
+        let valid_base = |base: f64| {
+            if base > 1.0 && base.is_finite() {
+                base
+            } else {
+                10.0
+            }
+        };
+        self.scale = match scale {
+            Scale::Log(base) => Scale::Log(valid_base(base)),
+            Scale::SymLog { base, threshold } => Scale::SymLog {
+                base: valid_base(base),
+                threshold: if threshold > 0.0 && threshold.is_finite() {
+                    threshold
+                } else {
+                    1.0
+                },
+            },
+            scale => scale,
+        };
+    }
+    /// Set how the major ticks are determined.
+    pub fn set_ticks(&mut self, ticks: Ticks) {
+        self.ticks = ticks;
+    }
+    /// Set the number of intervals between major ticks, zero or one disables minor ticks.
+    pub fn set_minor_ticks(&mut self, subdivisions: usize) {
+        self.minor_ticks = Some(subdivisions);
+    }
+    /// Set the length of the major and minor tick marks, in canvas units.
+    pub fn set_tick_length(&mut self, major: f64, minor: f64) {
+        self.major_tick_length = major;
+        self.minor_tick_length = minor;
+    }
 }
 
-impl From<Axis> for Box<(dyn Node + 'static)> {
+impl From<Axis> for Box<dyn Node + 'static> {
     fn from(val: Axis) -> Self {
         Box::new(val.svg())
     }
@@ -116,10 +748,20 @@ impl AxisHorizontal {
             horizontal: self.clone(),
         }
     }
-    fn project(&self, v: f64) -> f64 {
-        let z = self.0.borrow_mut();
+    /// Map a plot value to its canvas position on this axis.
+    pub fn project(&self, v: f64) -> f64 {
+        let z = self.0.borrow();
         z.project(v)
     }
+    /// Map a canvas position on this axis back to a plot value.
+    pub fn unproject(&self, p: f64) -> f64 {
+        let z = self.0.borrow();
+        z.unproject(p)
+    }
+    fn origin(&self) -> f64 {
+        let z = self.0.borrow();
+        z.origin()
+    }
 
     pub fn set_plot_range(&self, min: f64, max: f64) {
         let mut z = self.0.borrow_mut();
@@ -129,6 +771,40 @@ impl AxisHorizontal {
         let mut z = self.0.borrow_mut();
         z.set_canvas_range(min, max)
     }
+    /// Make this a categorical axis, bars of category i are centered on value i.
+    pub fn set_categories<T: Into<String> + Clone>(&self, categories: &[T]) {
+        let mut z = self.0.borrow_mut();
+        z.set_categories(categories)
+    }
+    pub fn set_scale(&self, scale: Scale) {
+        let mut z = self.0.borrow_mut();
+        z.set_scale(scale)
+    }
+    pub fn set_ticks(&self, ticks: Ticks) {
+        let mut z = self.0.borrow_mut();
+        z.set_ticks(ticks)
+    }
+    pub fn set_minor_ticks(&self, subdivisions: usize) {
+        let mut z = self.0.borrow_mut();
+        z.set_minor_ticks(subdivisions)
+    }
+    pub fn set_tick_length(&self, major: f64, minor: f64) {
+        let mut z = self.0.borrow_mut();
+        z.set_tick_length(major, minor)
+    }
+
+    /// Set an attribute on the tick labels.
+    pub fn set_tick_label<T, U>(self, name: T, value: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<Value>,
+    {
+        {
+            let mut z = self.0.borrow_mut();
+            z.tick_label_attributes.insert(name.into(), value.into());
+        }
+        self
+    }
 
     pub fn set<T, U>(self, name: T, value: U) -> Self
     where
@@ -159,10 +835,20 @@ impl AxisVertical {
             vertical: self.clone(),
         }
     }
-    fn project(&self, v: f64) -> f64 {
-        let z = self.0.borrow_mut();
+    /// Map a plot value to its canvas position on this axis.
+    pub fn project(&self, v: f64) -> f64 {
+        let z = self.0.borrow();
         z.project(v)
     }
+    /// Map a canvas position on this axis back to a plot value.
+    pub fn unproject(&self, p: f64) -> f64 {
+        let z = self.0.borrow();
+        z.unproject(p)
+    }
+    fn origin(&self) -> f64 {
+        let z = self.0.borrow();
+        z.origin()
+    }
 
     pub fn set_plot_range(&self, min: f64, max: f64) {
         let mut z = self.0.borrow_mut();
@@ -172,6 +858,35 @@ impl AxisVertical {
         let mut z = self.0.borrow_mut();
         z.set_canvas_range(min, max)
     }
+    pub fn set_scale(&self, scale: Scale) {
+        let mut z = self.0.borrow_mut();
+        z.set_scale(scale)
+    }
+    pub fn set_ticks(&self, ticks: Ticks) {
+        let mut z = self.0.borrow_mut();
+        z.set_ticks(ticks)
+    }
+    pub fn set_minor_ticks(&self, subdivisions: usize) {
+        let mut z = self.0.borrow_mut();
+        z.set_minor_ticks(subdivisions)
+    }
+    pub fn set_tick_length(&self, major: f64, minor: f64) {
+        let mut z = self.0.borrow_mut();
+        z.set_tick_length(major, minor)
+    }
+
+    /// Set an attribute on the tick labels.
+    pub fn set_tick_label<T, U>(self, name: T, value: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<Value>,
+    {
+        {
+            let mut z = self.0.borrow_mut();
+            z.tick_label_attributes.insert(name.into(), value.into());
+        }
+        self
+    }
     pub fn set<T, U>(self, name: T, value: U) -> Self
     where
         T: Into<String>,
@@ -197,27 +912,136 @@ pub struct Frame {
 impl Frame {
     fn svg(&self) -> Group {
         let mut group = Group::new();
-        let hsvg = self.horizontal.svg();
-        let vsvg = self.vertical.svg();
-        // hsvg.translate_xy(0.0, -self.vertical.project(0.0));
-        // vsvg.translate_xy(-self.horizontal.project(0.0), 0.0);
+        let mut hsvg = self.horizontal.svg();
+        let mut vsvg = self.vertical.svg();
+        // Let the axes cross at the data origin.
+        hsvg.translate_xy(0.0, self.vertical.origin());
+        vsvg.translate_xy(self.horizontal.origin(), 0.0);
         group.append(hsvg);
         group.append(vsvg);
         group
     }
 }
 
-impl From<&Frame> for Box<(dyn Node + 'static)> {
+impl From<&Frame> for Box<dyn Node + 'static> {
     fn from(val: &Frame) -> Self {
         Box::new(val.svg())
     }
 }
 
+/// The shape drawn at each point of a scatter series.
+#[derive(Debug, Clone, Default)]
+pub enum Marker {
+    #[default]
+    Circle,
+    Square,
+    Triangle,
+    Cross,
+    Diamond,
+    /// A user provided node, scaled by the marker size and placed with its origin on the point.
+    /// It should fit in a unit square centered on its origin.
+    Node(Box<dyn Node>),
+}
+
+impl Marker {
+    /// Create the marker node centered at x, y, size is the width of the marker.
+    pub fn svg(&self, x: f64, y: f64, size: f64) -> Box<dyn Node> {
+        let h = size / 2.0;
+        match self {
+            Marker::Circle => Box::new(Circle::new().set("cx", x).set("cy", y).set("r", h)),
+            Marker::Square => Box::new(
+                Rectangle::new()
+                    .set("x", x - h)
+                    .set("y", y - h)
+                    .set("width", size)
+                    .set("height", size),
+            ),
+            Marker::Triangle => {
+                // Equilateral, pointing up, centered on its centroid.
+                let height = size * 3.0f64.sqrt() / 2.0;
+                let data = Data::new()
+                    .move_to((x, y - height * 2.0 / 3.0))
+                    .line_to((x + h, y + height / 3.0))
+                    .line_to((x - h, y + height / 3.0))
+                    .close();
+                Box::new(Path::new().set("d", data))
+            }
+            Marker::Cross => {
+                let data = Data::new()
+                    .move_to((x - h, y - h))
+                    .line_to((x + h, y + h))
+                    .move_to((x - h, y + h))
+                    .line_to((x + h, y - h));
+                Box::new(Path::new().set("d", data))
+            }
+            Marker::Diamond => {
+                let data = Data::new()
+                    .move_to((x, y - h))
+                    .line_to((x + h, y))
+                    .line_to((x, y + h))
+                    .line_to((x - h, y))
+                    .close();
+                Box::new(Path::new().set("d", data))
+            }
+            Marker::Node(node) => {
+                let mut group = Group::new().add(node.clone());
+                group.assign(
+                    "transform",
+                    format!("translate({},{}) scale({})", x, y, size),
+                );
+                Box::new(group)
+            }
+        }
+    }
+
+    /// The attribute a color applies to, crosses consist only of strokes.
+    fn color_attribute(&self) -> &'static str {
+        match self {
+            Marker::Cross => "stroke",
+            _ => "fill",
+        }
+    }
+}
+
+const DEFAULT_MARKER_SIZE: f64 = 5.0;
+
+#[derive(Debug, Copy, Clone, Default)]
+enum DrawKind {
+    #[default]
+    Line,
+    Scatter,
+    /// Bars centered at offset from the x value, width in plot units.
+    Bar {
+        offset: f64,
+        width: f64,
+    },
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct DrawElement {
     frame: Frame,
     data: Vec<(f64, f64)>,
     attributes: Attributes,
+    kind: DrawKind,
+    marker: Marker,
+    marker_size: f64,
+    marker_sizes: Vec<f64>,
+    marker_colors: Vec<Value>,
+    bar_bases: Vec<f64>,
+    item_attributes: Vec<Attributes>,
+    label: Option<String>,
+    fill: Option<Paint>,
+}
+
+impl DrawElement {
+    /// The attribute the color of this series applies to.
+    fn color_attribute(&self) -> &'static str {
+        match self.kind {
+            DrawKind::Line => "stroke",
+            DrawKind::Scatter => self.marker.color_attribute(),
+            DrawKind::Bar { .. } => "fill",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -228,11 +1052,39 @@ impl DrawElementHandle {
         Self(Rc::new(RefCell::new(DrawElement {
             frame: frame.clone(),
             data: data.to_vec(),
-            attributes: Default::default(),
+            marker_size: DEFAULT_MARKER_SIZE,
+            ..Default::default()
         })))
     }
+
+    fn scatter(frame: &Frame, data: &[(f64, f64)]) -> DrawElementHandle {
+        let el = Self::new(frame, data);
+        el.0.borrow_mut().kind = DrawKind::Scatter;
+        el
+    }
+
+    fn bar(frame: &Frame, values: &[f64], offset: f64, width: f64, bases: &[f64]) -> Self {
+        let data: Vec<(f64, f64)> = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i as f64, *v))
+            .collect();
+        let el = Self::new(frame, &data);
+        {
+            let mut z = el.0.borrow_mut();
+            z.kind = DrawKind::Bar { offset, width };
+            z.bar_bases = bases.to_vec();
+        }
+        el
+    }
+
     pub fn svg(&self) -> Group {
         let z = self.0.borrow();
+        match z.kind {
+            DrawKind::Line => {}
+            DrawKind::Scatter => return self.svg_scatter(),
+            DrawKind::Bar { offset, width } => return self.svg_bar(offset, width),
+        }
         let mut group = Group::new();
         let mut points = String::new();
         for (x, y) in z.data.iter() {
@@ -249,6 +1101,63 @@ impl DrawElementHandle {
         group
     }
 
+    fn svg_scatter(&self) -> Group {
+        let z = self.0.borrow();
+        // Series attributes go on the group, such that all markers inherit them.
+        let mut group = Group::new();
+        let attr = group.get_attributes_mut().unwrap();
+        for (k, v) in z.attributes.iter() {
+            attr.insert(k.clone(), v.clone());
+        }
+        for (i, (x, y)) in z.data.iter().enumerate() {
+            let px = z.frame.horizontal.project(*x);
+            let py = z.frame.vertical.project(*y);
+            let size = z.marker_sizes.get(i).copied().unwrap_or(z.marker_size);
+            let mut marker = z.marker.svg(px, py, size);
+            if let Some(attr) = marker.get_attributes_mut() {
+                if let Some(color) = z.marker_colors.get(i) {
+                    attr.insert(z.marker.color_attribute().to_owned(), color.clone());
+                }
+                if let Some(item) = z.item_attributes.get(i) {
+                    for (k, v) in item.iter() {
+                        attr.insert(k.clone(), v.clone());
+                    }
+                }
+            }
+            group.append(marker);
+        }
+        group
+    }
+
+    fn svg_bar(&self, offset: f64, width: f64) -> Group {
+        let z = self.0.borrow();
+        let mut group = Group::new();
+        let attr = group.get_attributes_mut().unwrap();
+        for (k, v) in z.attributes.iter() {
+            attr.insert(k.clone(), v.clone());
+        }
+        for (i, (x, v)) in z.data.iter().enumerate() {
+            let base = z.bar_bases.get(i).copied().unwrap_or(0.0);
+            let x0 = z.frame.horizontal.project(x + offset - width / 2.0);
+            let x1 = z.frame.horizontal.project(x + offset + width / 2.0);
+            let y0 = z.frame.vertical.project(base);
+            let y1 = z.frame.vertical.project(base + v);
+            let mut rect = Rectangle::new()
+                .set("x", x0.min(x1))
+                .set("y", y0.min(y1))
+                .set("width", (x1 - x0).abs())
+                .set("height", (y1 - y0).abs());
+            if let Some(item) = z.item_attributes.get(i) {
+                let attr = rect.get_attributes_mut().unwrap();
+                for (k, v) in item.iter() {
+                    attr.insert(k.clone(), v.clone());
+                }
+            }
+            group.append(rect);
+        }
+        group
+    }
+
     pub fn set<T, U>(self, name: T, value: U) -> Self
     where
         T: Into<String>,
@@ -260,31 +1169,149 @@ impl DrawElementHandle {
         }
         self
     }
+
+    /// Set the label for this series, series with a label show up in the plot's legend.
+    pub fn set_label<T: Into<String>>(self, label: T) -> Self {
+        self.0.borrow_mut().label = Some(label.into());
+        self
+    }
+
+    /// A legend entry for this series, if it has a label.
+    fn legend_entry(&self) -> Option<LegendEntry> {
+        let z = self.0.borrow();
+        let label = z.label.clone()?;
+        let swatch = match z.kind {
+            DrawKind::Line => Swatch::Line,
+            DrawKind::Scatter => Swatch::Marker(z.marker.clone()),
+            DrawKind::Bar { .. } => Swatch::Square,
+        };
+        let mut entry = LegendEntry::new(label).swatch(swatch);
+        for (k, v) in z.attributes.iter() {
+            entry = entry.set(k.clone(), v.clone());
+        }
+        Some(entry)
+    }
+
+    /// Fill the series with paint, like a pattern or gradient, resolved when the plot is rendered.
+    pub fn set_fill<T: Into<Paint>>(self, paint: T) -> Self {
+        self.0.borrow_mut().fill = Some(paint.into());
+        self
+    }
+
+    /// Set an attribute on a single item of the series, like one bar of a bar series.
+    pub fn set_item<T, U>(self, index: usize, name: T, value: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<Value>,
+    {
+        {
+            let mut z = self.0.borrow_mut();
+            if z.item_attributes.len() <= index {
+                z.item_attributes.resize(index + 1, Default::default());
+            }
+            z.item_attributes[index].insert(name.into(), value.into());
+        }
+        self
+    }
+
+    /// Set the marker shape used for scatter series.
+    pub fn set_marker(self, marker: Marker) -> Self {
+        self.0.borrow_mut().marker = marker;
+        self
+    }
+
+    /// Set the marker size for all points of a scatter series.
+    pub fn set_marker_size(self, size: f64) -> Self {
+        self.0.borrow_mut().marker_size = size;
+        self
+    }
+
+    /// Set the marker size per point, points without a size use the series' marker size.
+    pub fn set_marker_sizes(self, sizes: &[f64]) -> Self {
+        self.0.borrow_mut().marker_sizes = sizes.to_vec();
+        self
+    }
+
+    /// Set the marker color per point, points without a color use the series' attributes.
+    pub fn set_marker_colors<T: Into<Value> + Clone>(self, colors: &[T]) -> Self {
+        self.0.borrow_mut().marker_colors = colors.iter().map(|c| c.clone().into()).collect();
+        self
+    }
 }
 
-impl From<&DrawElementHandle> for Box<(dyn Node + 'static)> {
+impl From<&DrawElementHandle> for Box<dyn Node + 'static> {
     fn from(val: &DrawElementHandle) -> Self {
         Box::new(val.svg())
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Plot {
     frames: Vec<Frame>,
     elements: Vec<DrawElementHandle>,
+    bar_width: f64,
+    palette: Option<Palette>,
+}
+
+impl Default for Plot {
+    fn default() -> Self {
+        Self {
+            frames: vec![],
+            elements: vec![],
+            bar_width: 0.8,
+            palette: None,
+        }
+    }
 }
 
 impl Plot {
+    /// Create a new plot, drawing elements in the provided frame.
     pub fn new(frame: &Frame) -> Self {
-        let _ = frame;
-        // Needs coordinate frame flip.
         Self {
             frames: vec![frame.clone()],
-            elements: vec![],
-        };
-        todo!("not ready for production yet")
+            ..Default::default()
+        }
+    }
+
+    /// Set the width of bars relative to the category width, used for bars created after this.
+    pub fn set_bar_width(&mut self, width: f64) {
+        self.bar_width = width;
+    }
+
+    /// Set a palette to color series that don't have a color set themselves.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = Some(palette);
+    }
+
+    /// The element as drawn, with its fill resolved if defs are provided, and with the palette
+    /// color if it has no color of its own.
+    fn colored(&self, index: usize, defs: Option<&mut Defs>) -> DrawElementHandle {
+        let el = &self.elements[index];
+        let z = el.0.borrow();
+        let fill = z.fill.as_ref().zip(defs);
+        let name = z.color_attribute();
+        let needs_color = self.palette.is_some() && !z.attributes.contains_key(name);
+        if fill.is_none() && !needs_color {
+            return el.clone();
+        }
+        let mut colored = z.clone();
+        if let Some((paint, defs)) = fill {
+            colored
+                .attributes
+                .insert("fill".to_owned(), paint.resolve(defs));
+        }
+        if let Some(palette) = &self.palette {
+            if !colored.attributes.contains_key(name) {
+                colored.attributes.insert(
+                    name.to_owned(),
+                    palette.color(index, self.elements.len()).into(),
+                );
+            }
+        }
+        DrawElementHandle(Rc::new(RefCell::new(colored)))
     }
 
+    /// Create a line through the provided data points, returns a handle to style it.
     pub fn line_xy(&mut self, data: &[(f64, f64)]) -> DrawElementHandle {
         let f = self.frames.first().unwrap();
         let el = DrawElementHandle::new(f, data);
@@ -292,11 +1319,99 @@ impl Plot {
         el
     }
 
+    /// Create a marker at each of the provided data points, returns a handle to style it.
+    pub fn scatter_xy(&mut self, data: &[(f64, f64)]) -> DrawElementHandle {
+        let f = self.frames.first().unwrap();
+        let el = DrawElementHandle::scatter(f, data);
+        self.elements.push(el.clone());
+        el
+    }
+
+    /// Create a bar for each value, the value's index is its category, returns a handle to style it.
+    pub fn bar(&mut self, values: &[f64]) -> DrawElementHandle {
+        let f = self.frames.first().unwrap();
+        let el = DrawElementHandle::bar(f, values, 0.0, self.bar_width, &[]);
+        self.elements.push(el.clone());
+        el
+    }
+
+    /// Create bars for multiple series, placed next to each other within each category.
+    pub fn bars_grouped(&mut self, series: &[&[f64]]) -> Vec<DrawElementHandle> {
+        let f = self.frames.first().unwrap().clone();
+        let width = self.bar_width / series.len() as f64;
+        let mut handles = vec![];
+        for (k, values) in series.iter().enumerate() {
+            let offset = -self.bar_width / 2.0 + (k as f64 + 0.5) * width;
+            let el = DrawElementHandle::bar(&f, values, offset, width, &[]);
+            self.elements.push(el.clone());
+            handles.push(el);
+        }
+        handles
+    }
+
+    /// Create bars for multiple series, stacked on top of each other within each category.
+    ///
+    /// Positive and negative values are stacked separately, upwards and downwards from zero.
+    pub fn bars_stacked(&mut self, series: &[&[f64]]) -> Vec<DrawElementHandle> {
+        let f = self.frames.first().unwrap().clone();
+        let categories = series.iter().map(|s| s.len()).max().unwrap_or(0);
+        let mut positive = vec![0.0; categories];
+        let mut negative = vec![0.0; categories];
+        let mut handles = vec![];
+        for values in series.iter() {
+            let bases: Vec<f64> = values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let stack = if *v < 0.0 {
+                        &mut negative[i]
+                    } else {
+                        &mut positive[i]
+                    };
+                    let base = *stack;
+                    *stack += v;
+                    base
+                })
+                .collect();
+            let el = DrawElementHandle::bar(&f, values, 0.0, self.bar_width, &bases);
+            self.elements.push(el.clone());
+            handles.push(el);
+        }
+        handles
+    }
+
+    /// Create a legend with an entry for each labeled series, styled like the series.
+    ///
+    /// Fills set with paint are not shown, use legend_with_defs for those.
+    pub fn legend(&self) -> Legend {
+        (0..self.elements.len())
+            .filter_map(|i| self.colored(i, None).legend_entry())
+            .fold(Legend::new(), |legend, entry| legend.entry(entry))
+    }
+
+    /// Create a legend like legend, adding the definitions needed by fills to defs.
+    pub fn legend_with_defs(&self, defs: &mut Defs) -> Legend {
+        (0..self.elements.len())
+            .filter_map(|i| self.colored(i, Some(defs)).legend_entry())
+            .fold(Legend::new(), |legend, entry| legend.entry(entry))
+    }
+
+    /// Render the plot to svg, definitions needed by fills are included in the group.
     pub fn svg(&self) -> Group {
+        let mut defs = Defs::new();
+        let plot = self.svg_with_defs(&mut defs);
+        if defs.is_empty() {
+            return plot;
+        }
+        Group::new().add(defs).add(plot)
+    }
+
+    /// Render the plot to svg, adding the definitions needed by fills to defs.
+    pub fn svg_with_defs(&self, defs: &mut Defs) -> Group {
         let mut group = Group::new();
 
-        for el in self.elements.iter() {
-            group.append(el)
+        for i in 0..self.elements.len() {
+            group.append(&self.colored(i, Some(defs)))
         }
         for f in self.frames.iter() {
             group.append(f)
@@ -305,7 +1420,7 @@ impl Plot {
     }
 }
 
-impl From<Plot> for Box<(dyn Node + 'static)> {
+impl From<Plot> for Box<dyn Node + 'static> {
     fn from(val: Plot) -> Self {
         Box::new(val.svg())
     }
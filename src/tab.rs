@@ -1,4 +1,5 @@
-use svg::node::element::Path;
+use crate::color::Color;
+use svg::node::element::{Path, Text};
 
 use svg::node::element::path::Data;
 
@@ -38,11 +39,72 @@ pub enum TabEdge {
     None,
 }
 
+/// How a corner of the tab is drawn.
+#[derive(Clone, Copy, Default, Debug, PartialEq, PartialOrd, Eq)]
+pub enum CornerStyle {
+    /// An arc of the corner's radius.
+    #[default]
+    Round,
+    /// A straight cut, starting at the corner's radius from the corner along both edges.
+    Chamfer,
+    /// No rounding, the radius is ignored.
+    Sharp,
+}
+
+/// A single corner of the tab.
+#[derive(Clone, Copy, Default, Debug, PartialEq, PartialOrd)]
+pub struct Corner {
+    /// How the corner is drawn.
+    pub style: CornerStyle,
+    /// The radius for round corners, the setback along the edges for chamfered corners.
+    pub radius: f64,
+}
+
+impl Corner {
+    /// A corner rounded with the provided radius.
+    pub fn round(radius: f64) -> Self {
+        Corner {
+            style: CornerStyle::Round,
+            radius,
+        }
+    }
+    /// A corner cut off at the provided distance from the corner.
+    pub fn chamfer(size: f64) -> Self {
+        Corner {
+            style: CornerStyle::Chamfer,
+            radius: size,
+        }
+    }
+    /// A corner without rounding.
+    pub fn sharp() -> Self {
+        Corner {
+            style: CornerStyle::Sharp,
+            radius: 0.0,
+        }
+    }
+}
+
+/// Denotes one of the corners of the tab's body.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq)]
+pub enum BodyCorner {
+    TopLeft = 0,
+    TopRight = 1,
+    BottomRight = 2,
+    BottomLeft = 3,
+}
+
 /// The tab's properties.
+///
+/// Each corner has its own style and radius, these fields replace the single `radius` field. Use
+/// [`Tab::radius`] or [`Tab::set_radius`] to give all corners the same radius.
 #[derive(Clone, Copy, Default, Debug, PartialEq, PartialOrd)]
 pub struct Tab {
-    /// The rounding radius.
-    pub radius: f64,
+    /// The corners of the body, indexed by [`BodyCorner`].
+    pub body_corners: [Corner; 4],
+    /// The outer corners of the tab protrusion, ordered by increasing position along the edge.
+    pub tab_corners: [Corner; 2],
+    /// The inner corners where the protrusion joins the body, ordered like the tab corners.
+    pub fillet_corners: [Corner; 2],
     /// The width of the tab (without protrusion).
     pub width: f64,
     /// The height of the tab (without protrusion).
@@ -68,9 +130,43 @@ impl Tab {
         self.height = height;
         self
     }
-    /// Returns a tab with the radius set to the provided value.
+    /// Returns a tab with the radius of all corners set to the provided value.
     pub fn radius(mut self, radius: f64) -> Self {
-        self.radius = radius;
+        self.set_radius(radius);
+        self
+    }
+    /// Set the radius of all corners, keeping their styles.
+    pub fn set_radius(&mut self, radius: f64) {
+        for c in self.corners_mut() {
+            c.radius = radius;
+        }
+    }
+    /// Returns a tab with all corners set to the provided corner.
+    pub fn corners(mut self, corner: Corner) -> Self {
+        for c in self.corners_mut() {
+            *c = corner;
+        }
+        self
+    }
+    /// Returns a tab with the four corners of the body set to the provided corner.
+    pub fn body_corners(mut self, corner: Corner) -> Self {
+        self.body_corners = [corner; 4];
+        self
+    }
+    /// Returns a tab with a single corner of the body set to the provided corner.
+    pub fn body_corner(mut self, which: BodyCorner, corner: Corner) -> Self {
+        self.body_corners[which as usize] = corner;
+        self
+    }
+    /// Returns a tab with the outer corners of the protrusion set to the provided corner.
+    pub fn tab_corners(mut self, corner: Corner) -> Self {
+        self.tab_corners = [corner; 2];
+        self
+    }
+    /// Returns a tab with the inner corners, where the protrusion joins the body, set to the
+    /// provided corner.
+    pub fn fillet_corners(mut self, corner: Corner) -> Self {
+        self.fillet_corners = [corner; 2];
         self
     }
     /// Returns a tab with the tab's width and height set to the provided values.
@@ -91,279 +187,192 @@ impl Tab {
         self
     }
 
-    /// Returns the svg path that creates the tab shape.
-    pub fn svg(&self) -> Path {
-        let r = self.radius;
-
-        /*
-
-
-                     O-A------------B--+
-                     P                 |
-                     |                 |
-                     O                 C
-               ^ +M-N+                 |
-         tab   : L                     |
-         height: |                     |
-               : |                     |
-               : |                     |
-               : K                     |
-               : |                     |
-               v +-J-I-+               |
-                       |               |
-                       H               |
-                       |               |
-                       G               D
-                       |               |
-                       +--F---------E--+
+    fn corners_mut(&mut self) -> impl Iterator<Item = &mut Corner> {
+        self.body_corners
+            .iter_mut()
+            .chain(self.tab_corners.iter_mut())
+            .chain(self.fillet_corners.iter_mut())
+    }
 
-                 tab width
-        */
+    /// The outline as a clockwise polygon, starting at the top left corner of the body.
+    fn outline(&self) -> Vec<((f64, f64), Corner)> {
+        let (w, h) = (self.width, self.height);
+        let (pos, tw, th) = (self.tab_position, self.tab_width, self.tab_height);
+        let [tl, tr, br, bl] = self.body_corners;
+        let [tab_start, tab_end] = self.tab_corners;
+        let [fillet_start, fillet_end] = self.fillet_corners;
 
-        #[derive(Default)]
-        struct TabData {
-            a: (f64, f64),
-            b: (f64, f64),
-            c: (f64, f64),
-            d: (f64, f64),
-            e: (f64, f64),
-            f: (f64, f64),
-            g: (f64, f64),
-            h: (f64, f64),
-            i: (f64, f64),
-            j: (f64, f64),
-            k: (f64, f64),
-            l: (f64, f64),
-            m: (f64, f64),
-            n: (f64, f64),
-            o: (f64, f64),
-            p: (f64, f64),
-            tab_not_near_end: bool,
-            tab_not_near_start: bool,
-            has_tab: bool,
+        let mut points = vec![((0.0, 0.0), tl)];
+        let has_tab = !matches!(self.tab_edge, TabEdge::None) && (tw != 0.0 && th != 0.0);
+        if has_tab && self.tab_edge == TabEdge::Top {
+            points.push(((pos, 0.0), fillet_start));
+            points.push(((pos, -th), tab_start));
+            points.push(((pos + tw, -th), tab_end));
+            points.push(((pos + tw, 0.0), fillet_end));
+        }
+        points.push(((w, 0.0), tr));
+        if has_tab && self.tab_edge == TabEdge::Right {
+            points.push(((w, pos), fillet_start));
+            points.push(((w + tw, pos), tab_start));
+            points.push(((w + tw, pos + th), tab_end));
+            points.push(((w, pos + th), fillet_end));
+        }
+        points.push(((w, h), br));
+        // The bottom and left edges are traversed against the direction of the position.
+        if has_tab && self.tab_edge == TabEdge::Bottom {
+            points.push(((pos + tw, h), fillet_end));
+            points.push(((pos + tw, h + th), tab_end));
+            points.push(((pos, h + th), tab_start));
+            points.push(((pos, h), fillet_start));
+        }
+        points.push(((0.0, h), bl));
+        if has_tab && self.tab_edge == TabEdge::Left {
+            points.push(((0.0, pos + th), fillet_end));
+            points.push(((-tw, pos + th), tab_end));
+            points.push(((-tw, pos), tab_start));
+            points.push(((0.0, pos), fillet_start));
         }
-        let has_tab = !matches!(self.tab_edge, TabEdge::None)
-            && (self.tab_width != 0.0 && self.tab_height != 0.0);
 
-        let d = match self.tab_edge {
-            TabEdge::Left => {
-                TabData {
-                    a: (r, 0.0),
-                    b: (self.width - r, 0.0),
-                    c: (self.width, r),
-                    d: (self.width, self.height - r),
-                    e: (self.width - r, self.height),
-                    f: (r, self.height),
-                    g: (0.0, self.height - r),
-                    h: (0.0, self.tab_position + 1.0 * r + self.tab_height),
-                    i: (-r, self.tab_position + 0.0 * r + self.tab_height),
-                    j: (
-                        -self.tab_width + r,
-                        self.tab_position + 0.0 * r + self.tab_height,
-                    ),
-                    k: (
-                        -self.tab_width,
-                        self.tab_position - 1.0 * r + self.tab_height,
-                    ),
-                    l: (-self.tab_width, self.tab_position + 1.0 * r),
-                    m: (-self.tab_width + r, self.tab_position),
-                    n: (-r, self.tab_position),
-                    o: (0.0, self.tab_position - r),
-                    p: (0.0, r),
-                    tab_not_near_end: (self.tab_position + 1.0 * r + self.tab_height) < self.height,
-                    tab_not_near_start: self.tab_position > r,
-                    has_tab: has_tab,
-                    // ..Default::default()
-                }
-            }
-            TabEdge::Top => {
-                // Previously, we had the tab on the left, and start with the top.
-                // Now the tab is on the top, so we start with the right.
-                // rotate all points?
-                TabData {
-                    a: (self.width, r),
-                    b: (self.width, self.height - r),
-                    c: (self.width - r, self.height),
-                    d: (r, self.height),
-                    e: (0.0, self.height - r), // bottom left corner done.
-                    f: (0.0, r),               // to start of top left corner.
-                    g: (r, 0.0),
-                    h: (self.tab_position - 1.0 * r, 0.0),
-                    i: (self.tab_position, -r),
-                    j: (self.tab_position, -self.tab_height + r),
-                    k: (self.tab_position + r, -self.tab_height), // arc to
-                    l: (self.tab_position + self.tab_width - r, -self.tab_height), // tab straight.
-                    m: (self.tab_position + self.tab_width, -self.tab_height + r),
-                    n: (self.tab_position + self.tab_width, -r),
-                    o: (self.tab_position + self.tab_width + r, 0.0),
-                    p: (self.width - r, 0.0),
-                    tab_not_near_end: (self.tab_position - r) > 0.0,
-                    tab_not_near_start: (self.tab_position + 1.0 * r + self.tab_width) < self.width,
-                    has_tab: has_tab,
-                    // ..Default::default()
-                }
-            }
-            TabEdge::Right => {
-                TabData {
-                    a: (self.width - r, self.height), // start of bottom edge.
-                    b: (r, self.height),              // bottom left arc start
-                    c: (0.0, self.height - r),        // bottom left arc end
-                    d: (0.0, r),                      // top left arc start
-                    e: (r, 0.0),                      // top left arc end
-                    f: (self.width - r, 0.0),
-                    g: (self.width, r),
-                    h: (self.width, self.tab_position - r),
-                    i: (self.width + r, self.tab_position),
-                    j: (self.width + self.tab_width - r, self.tab_position),
-                    k: (self.width + self.tab_width, self.tab_position + r),
-                    l: (
-                        self.width + self.tab_width,
-                        self.tab_position + self.tab_height - r,
-                    ),
-                    m: (
-                        self.width + self.tab_width - r,
-                        self.tab_position + self.tab_height,
-                    ),
-                    n: (self.width + r, self.tab_position + self.tab_height), // straight to last arc.
-                    o: (self.width, self.tab_position + self.tab_height + r),
-                    p: (self.width, self.height - r),
-                    tab_not_near_end: self.tab_position > r,
-                    tab_not_near_start: (self.tab_position + 1.0 * r + self.tab_height)
-                        < self.height,
-                    has_tab: has_tab,
-                    // ..Default::default()
-                }
-            }
-            TabEdge::Bottom => {
-                TabData {
-                    a: (0.0, self.height - r), // bottom left arc end
-                    b: (0.0, r),               // top left arc start
-                    c: (r, 0.0),               // top left arc end
-                    d: (self.width - r, 0.0),
-                    e: (self.width, r),
-                    f: (self.width, self.height - r),
-                    g: (self.width - r, self.height),
-                    h: (self.tab_position + self.tab_width + 1.0 * r, self.height),
-                    i: (self.tab_position + self.tab_width, self.height + r),
-                    j: (
-                        self.tab_position + self.tab_width,
-                        self.height + self.tab_height - r,
-                    ),
-                    k: (
-                        self.tab_position + self.tab_width - r,
-                        self.height + self.tab_height,
-                    ), // arc to
-                    l: (self.tab_position + r, self.height + self.tab_height), // tab straight.
-                    m: (self.tab_position, self.height + self.tab_height - r),
-                    n: (self.tab_position, self.height + r),
-                    o: (self.tab_position - r, self.height), // start of bottom edge.
-                    p: (r, self.height),                     // bottom left arc start
-                    tab_not_near_end: (self.tab_position + 1.0 * r + self.tab_width) < self.width,
-                    tab_not_near_start: self.tab_position > r,
-                    has_tab: has_tab,
+        // A tab flush with a body corner makes for coinciding or collinear points, these are not
+        // corners.
+        let mut changed = true;
+        while changed && points.len() > 2 {
+            changed = false;
+            for i in 0..points.len() {
+                let prev = points[(i + points.len() - 1) % points.len()].0;
+                let (p, _) = points[i];
+                let next = points[(i + 1) % points.len()].0;
+                let u = (p.0 - prev.0, p.1 - prev.1);
+                let v = (next.0 - p.0, next.1 - p.1);
+                let coincides = u == (0.0, 0.0);
+                let straight = u.0 * v.1 - u.1 * v.0 == 0.0 && u.0 * v.0 + u.1 * v.1 > 0.0;
+                if coincides || straight {
+                    points.remove(i);
+                    changed = true;
+                    break;
                 }
             }
-            TabEdge::None => {
-                // Super gross, but hey it works.
-                let mut c = self.clone();
-                c.tab_width = 0.0;
-                c.tab_height = 0.0;
-                c.tab_edge = TabEdge::Left;
-                return c.svg();
-            }
+        }
+        points
+    }
+
+    /// Returns a label centered on the tab protrusion, or on the body if there is none.
+    ///
+    /// The text is black or white, whichever is readable on the fill of the tab.
+    pub fn label<T: Into<String>>(&self, text: T, fill: Color) -> Text {
+        let (w, h) = (self.width, self.height);
+        let (pos, tw, th) = (self.tab_position, self.tab_width, self.tab_height);
+        let has_tab = tw != 0.0 && th != 0.0;
+        let (x, y) = match self.tab_edge {
+            TabEdge::Left if has_tab => (-tw / 2.0, pos + th / 2.0),
+            TabEdge::Right if has_tab => (w + tw / 2.0, pos + th / 2.0),
+            TabEdge::Top if has_tab => (pos + tw / 2.0, -th / 2.0),
+            TabEdge::Bottom if has_tab => (pos + tw / 2.0, h + th / 2.0),
+            _ => (w / 2.0, h / 2.0),
         };
+        Text::new(text)
+            .set("x", x)
+            .set("y", y)
+            .set("text-anchor", "middle")
+            .set("dominant-baseline", "middle")
+            .set("fill", fill.contrasting())
+    }
 
-        let mut data = Data::new()
-            .move_to(d.a) // start at radius offset
-            .line_to(d.b) // straight line at top to first curve.
-            .elliptical_arc_to((
-                r, r,
-                0.0, // x axis rotation of the ellipse
-                0, 1, // large flag arc, sweep flag
-                d.c.0,
-                d.c.1,
-            ))
-            .line_to(d.d) // straight line to second curve
-            .elliptical_arc_to((
-                r, r,
-                0.0, // x axis rotation of the ellipse
-                0,
-                1, // large flag arc, sweep flag
-                d.e.0,
-                d.e.1,
-            ))
-            .line_to(d.f) // straight line to third curve
-            ;
+    /// Returns the svg path that creates the tab shape.
+    pub fn svg(&self) -> Path {
+        let points = self.outline();
+        let n = points.len();
+        let at = |i: usize| points[i % n].0;
+        let length = |a: (f64, f64), b: (f64, f64)| (b.0 - a.0).hypot(b.1 - a.1);
 
-        if d.has_tab {
-            if d.tab_not_near_end {
-                data = data
-                    .elliptical_arc_to((
-                        r, r, 0.0, // x axis rotation of the ellipse
-                        0, 1, // large flag arc, sweep flag
-                        d.g.0, d.g.1,
-                    ))
-                    .line_to(d.h)
-                    .elliptical_arc_to((
-                        // first arc towards tab.
-                        r, r, 0.0, // x axis rotation of the ellipse
-                        0, 0, // large flag arc, sweep flag
-                        d.i.0, d.i.1,
-                    ));
+        // The turn at each corner, positive is convex as the outline is clockwise.
+        let turns: Vec<f64> = (0..n)
+            .map(|i| {
+                let (prev, p, next) = (at(i + n - 1), at(i), at(i + 1));
+                let u = (p.0 - prev.0, p.1 - prev.1);
+                let v = (next.0 - p.0, next.1 - p.1);
+                (u.0 * v.1 - u.1 * v.0).atan2(u.0 * v.0 + u.1 * v.1)
+            })
+            .collect();
+
+        // Distance from each corner along its edges where the corner starts and ends.
+        let setbacks: Vec<f64> = (0..n)
+            .map(|i| {
+                let corner = points[i].1;
+                match corner.style {
+                    CornerStyle::Round => corner.radius * (turns[i].abs() / 2.0).tan(),
+                    CornerStyle::Chamfer => corner.radius,
+                    CornerStyle::Sharp => 0.0,
+                }
+                .max(0.0)
+            })
+            .collect();
+        // Corners sharing an edge that are too large for it are both shrunk proportionally.
+        let fit = |i: usize| {
+            let (a, b) = (setbacks[i % n], setbacks[(i + 1) % n]);
+            let available = length(at(i), at(i + 1));
+            if a + b > available {
+                available / (a + b)
+            } else {
+                1.0
             }
+        };
+        let setbacks: Vec<f64> = (0..n)
+            .map(|i| setbacks[i] * fit(i + n - 1).min(fit(i)))
+            .collect();
 
-            data = data
-                .line_to(d.j)
-                .elliptical_arc_to((
-                    r, r, 0.0, // x axis rotation of the ellipse
-                    0, 1, // large flag arc, sweep flag
-                    d.k.0, d.k.1,
-                ))
-                .line_to(d.l)
-                .elliptical_arc_to((
-                    r, r, 0.0, // x axis rotation of the ellipse
-                    0, 1, // large flag arc, sweep flag
-                    d.m.0, d.m.1,
-                ))
-                .line_to(d.n);
+        let towards = |from: (f64, f64), to: (f64, f64), distance: f64| {
+            let l = length(from, to);
+            if l == 0.0 {
+                from
+            } else {
+                (
+                    from.0 + (to.0 - from.0) * distance / l,
+                    from.1 + (to.1 - from.1) * distance / l,
+                )
+            }
+        };
 
-            // Prevent the uglyness if the tab position is at the lower edge (0.0).
-            if d.tab_not_near_start {
-                data = data
-                    .elliptical_arc_to((
-                        r, r, 0.0, // x axis rotation of the ellipse
-                        0, 0, // large flag arc, sweep flag
-                        d.o.0, d.o.1,
+        let mut current = towards(at(0), at(1), setbacks[0]);
+        let mut data = Data::new().move_to(current);
+        for i in 1..=n {
+            let (p, corner) = points[i % n];
+            let setback = setbacks[i % n];
+            let start = towards(p, at(i - 1), setback);
+            let end = towards(p, at(i + 1), setback);
+            // Adjacent corners that meet leave no straight edge in between.
+            if length(current, start) > 1e-9 {
+                data = data.line_to(start);
+            }
+            current = end;
+            if setback == 0.0 {
+                continue;
+            }
+            data = match corner.style {
+                CornerStyle::Round => {
+                    let turn = turns[i % n];
+                    let r = setback / (turn.abs() / 2.0).tan();
+                    data.elliptical_arc_to((
+                        r,
+                        r,
+                        0.0, // x axis rotation of the ellipse
+                        0,
+                        if turn > 0.0 { 1 } else { 0 }, // large flag arc, sweep flag
+                        end.0,
+                        end.1,
                     ))
-                    .line_to(d.p) //Line to tab start.
-                    .elliptical_arc_to((
-                        r, r, 0.0, // x axis rotation of the ellipse
-                        0, 1, // large flag arc, sweep flag
-                        d.a.0, d.a.1,
-                    ));
+                }
+                CornerStyle::Chamfer => data.line_to(end),
+                CornerStyle::Sharp => data,
             };
-        } else {
-            data = data
-                .elliptical_arc_to((
-                    r, r, 0.0, // x axis rotation of the ellipse
-                    0, 1, // large flag arc, sweep flag
-                    d.g.0, d.g.1,
-                ))
-                .line_to(d.p)
-                .elliptical_arc_to((
-                    r, r, 0.0, // x axis rotation of the ellipse
-                    0, 1, // large flag arc, sweep flag
-                    d.a.0, d.a.1,
-                ));
         }
-
         data = data.close();
 
         Path::new().set("d", data)
     }
 }
 
-impl From<Tab> for Box<(dyn svg::Node + 'static)> {
+impl From<Tab> for Box<dyn svg::Node + 'static> {
     fn from(val: Tab) -> Self {
         Box::new(val.svg())
     }
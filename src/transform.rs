@@ -1,10 +1,60 @@
+/// Transforms compose, each call appends to the element's transform list.
+///
+/// Like in svg, the last transform in the list is applied first, in the element's own coordinates.
+/// So `translated_xy(80.0, 0.0).rotated(45)` rotates the element about its own origin and then
+/// moves it.
 pub trait Transformed: svg::Node {
+    /// The current transform list of this element, if any.
+    fn transform(&self) -> Option<String> {
+        self.get_attributes()
+            .and_then(|a| a.get("transform"))
+            .map(|v| v.to_string())
+    }
+
+    /// The current transform list of this element as a matrix, the identity if it has none.
+    fn affine(&self) -> Result<Affine, ParseTransformError> {
+        self.transform()
+            .map_or(Ok(Affine::IDENTITY), |t| Affine::parse(&t))
+    }
+
+    /// Remove all transforms from this element.
+    fn reset_transform(&mut self)
+    where
+        Self: Sized,
+    {
+        if let Some(attributes) = self.get_attributes_mut() {
+            attributes.remove("transform");
+        }
+    }
+
+    /// Append a transform, like `skewX(10)`, to the transform list of this element.
+    fn append_transform<T: Into<svg::node::Value>>(&mut self, transform: T)
+    where
+        Self: Sized,
+    {
+        let transform = transform.into().to_string();
+        let list = match self.transform() {
+            Some(existing) if !existing.is_empty() => format!("{} {}", existing, transform),
+            _ => transform,
+        };
+        self.assign("transform", list);
+    }
+    /// Append a transform, like `skewX(10)`, to the transform list of this element.
+    fn transformed<T: Into<svg::node::Value>>(self, transform: T) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.append_transform(transform);
+        z
+    }
+
     /// Translate this element in x direction by the provided value.
     fn translate_x<T: Into<svg::node::Value>>(&mut self, x: T)
     where
         Self: Sized,
     {
-        self.assign("transform", format!("translate({},0.0)", x.into()));
+        self.append_transform(format!("translate({},0.0)", x.into()));
     }
     /// Translate this element in x direction by the provided value.
     fn translated_x<T: Into<svg::node::Value>>(self, x: T) -> Self
@@ -21,7 +71,7 @@ pub trait Transformed: svg::Node {
     where
         Self: Sized,
     {
-        self.assign("transform", format!("translate(0.0,{})", y.into()));
+        self.append_transform(format!("translate(0.0,{})", y.into()));
     }
     /// Translate this element in y direction by the provided value.
     fn translated_y<T: Into<svg::node::Value>>(self, y: T) -> Self
@@ -38,7 +88,7 @@ pub trait Transformed: svg::Node {
     where
         Self: Sized,
     {
-        self.assign("transform", format!("translate({},{})", x.into(), y.into()));
+        self.append_transform(format!("translate({},{})", x.into(), y.into()));
     }
     /// Translate this element in x and y direction by the provided value.
     fn translated_xy<T: Into<svg::node::Value>, U: Into<svg::node::Value>>(self, x: T, y: U) -> Self
@@ -55,7 +105,7 @@ pub trait Transformed: svg::Node {
     where
         Self: Sized,
     {
-        self.assign("transform", format!("rotate({})", x.into()));
+        self.append_transform(format!("rotate({})", x.into()));
     }
 
     /// A rotated transform, rotates by x degrees.
@@ -67,7 +117,502 @@ pub trait Transformed: svg::Node {
         z.rotate(x);
         z
     }
+
+    /// Rotate this element by x degrees about the point (cx, cy).
+    fn rotate_about<T, U, V>(&mut self, x: T, cx: U, cy: V)
+    where
+        Self: Sized,
+        T: Into<svg::node::Value>,
+        U: Into<svg::node::Value>,
+        V: Into<svg::node::Value>,
+    {
+        self.append_transform(format!("rotate({},{},{})", x.into(), cx.into(), cy.into()));
+    }
+    /// A rotated transform, rotates by x degrees about the point (cx, cy).
+    fn rotated_about<T, U, V>(self, x: T, cx: U, cy: V) -> Self
+    where
+        Self: Sized,
+        T: Into<svg::node::Value>,
+        U: Into<svg::node::Value>,
+        V: Into<svg::node::Value>,
+    {
+        let mut z = self;
+        z.rotate_about(x, cx, cy);
+        z
+    }
+
+    /// Scale this element uniformly by the provided factor.
+    fn scale<T: Into<svg::node::Value>>(&mut self, s: T)
+    where
+        Self: Sized,
+    {
+        self.append_transform(format!("scale({})", s.into()));
+    }
+    /// Scale this element uniformly by the provided factor.
+    fn scaled<T: Into<svg::node::Value>>(self, s: T) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.scale(s);
+        z
+    }
+
+    /// Scale this element by x in x direction and by y in y direction.
+    fn scale_xy<T: Into<svg::node::Value>, U: Into<svg::node::Value>>(&mut self, x: T, y: U)
+    where
+        Self: Sized,
+    {
+        self.append_transform(format!("scale({},{})", x.into(), y.into()));
+    }
+    /// Scale this element by x in x direction and by y in y direction.
+    fn scaled_xy<T: Into<svg::node::Value>, U: Into<svg::node::Value>>(self, x: T, y: U) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.scale_xy(x, y);
+        z
+    }
+
+    /// Skew this element along the x axis by x degrees.
+    fn skew_x<T: Into<svg::node::Value>>(&mut self, x: T)
+    where
+        Self: Sized,
+    {
+        self.append_transform(format!("skewX({})", x.into()));
+    }
+    /// Skew this element along the x axis by x degrees.
+    fn skewed_x<T: Into<svg::node::Value>>(self, x: T) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.skew_x(x);
+        z
+    }
+
+    /// Skew this element along the y axis by y degrees.
+    fn skew_y<T: Into<svg::node::Value>>(&mut self, y: T)
+    where
+        Self: Sized,
+    {
+        self.append_transform(format!("skewY({})", y.into()));
+    }
+    /// Skew this element along the y axis by y degrees.
+    fn skewed_y<T: Into<svg::node::Value>>(self, y: T) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.skew_y(y);
+        z
+    }
+
+    /// Mirror this element left to right, about the y axis.
+    fn flip_horizontal(&mut self)
+    where
+        Self: Sized,
+    {
+        self.scale_xy(-1, 1);
+    }
+    /// Mirror this element left to right, about the y axis.
+    fn flipped_horizontal(self) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.flip_horizontal();
+        z
+    }
+
+    /// Mirror this element upside down, about the x axis.
+    fn flip_vertical(&mut self)
+    where
+        Self: Sized,
+    {
+        self.scale_xy(1, -1);
+    }
+    /// Mirror this element upside down, about the x axis.
+    fn flipped_vertical(self) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.flip_vertical();
+        z
+    }
+
+    /// Transform this element by the matrix [a c e; b d f; 0 0 1].
+    fn matrix(&mut self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64)
+    where
+        Self: Sized,
+    {
+        self.append_transform(format!("matrix({},{},{},{},{},{})", a, b, c, d, e, f));
+    }
+    /// Transform this element by the matrix [a c e; b d f; 0 0 1].
+    fn with_matrix(self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self
+    where
+        Self: Sized,
+    {
+        let mut z = self;
+        z.matrix(a, b, c, d, e, f);
+        z
+    }
 }
 
 // Blanket implementation for all nodes, since transform is applicable to anything.
 impl<T: svg::node::Node> Transformed for T {}
+
+/// Error returned when a string can't be parsed as a transform list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTransformError(String);
+
+impl std::fmt::Display for ParseTransformError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid transform {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseTransformError {}
+
+/// An affine transform, the matrix [a c e; b d f; 0 0 1] as used by svg's `matrix()`.
+///
+/// Multiplication follows svg's transform lists, in `x * y` the transform y is applied first.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Affine {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+/// An affine transform split into parts, equal to translate * rotate * skewX * scale.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Decomposed {
+    pub translate: (f64, f64),
+    /// Rotation in degrees.
+    pub rotation: f64,
+    /// Skew along the x axis in degrees.
+    pub skew_x: f64,
+    /// Scale factors, a mirrored transform has a negative y scale.
+    pub scale: (f64, f64),
+}
+
+impl Decomposed {
+    /// Combine the parts back into a single transform.
+    pub fn affine(&self) -> Affine {
+        Affine::translate(self.translate.0, self.translate.1)
+            * Affine::rotate(self.rotation)
+            * Affine::skew_x(self.skew_x)
+            * Affine::scale(self.scale.0, self.scale.1)
+    }
+}
+
+/// Tolerance for comparing matrix entries when looking for a shorter form.
+const AFFINE_EPSILON: f64 = 1e-9;
+
+/// Format a number for a transform attribute, without float noise like 0.30000000000000004.
+pub(crate) fn format_number(v: f64) -> String {
+    let rounded = (v * 1e9).round() / 1e9;
+    if rounded == 0.0 {
+        // Also prevents "-0".
+        "0".to_owned()
+    } else {
+        format!("{}", rounded)
+    }
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Affine::IDENTITY
+    }
+}
+
+impl Affine {
+    pub const IDENTITY: Affine = Affine::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    /// Create a transform from the entries of the matrix [a c e; b d f; 0 0 1].
+    pub const fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Affine { a, b, c, d, e, f }
+    }
+
+    /// A translation by x and y.
+    pub fn translate(x: f64, y: f64) -> Self {
+        Affine::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// A scale by x in x direction and by y in y direction.
+    pub fn scale(x: f64, y: f64) -> Self {
+        Affine::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// A rotation by angle degrees, clockwise in svg's coordinates where y points down.
+    pub fn rotate(angle: f64) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Affine::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// A rotation by angle degrees about the point (cx, cy).
+    pub fn rotate_about(angle: f64, cx: f64, cy: f64) -> Self {
+        Affine::translate(cx, cy) * Affine::rotate(angle) * Affine::translate(-cx, -cy)
+    }
+
+    /// A skew along the x axis by angle degrees.
+    pub fn skew_x(angle: f64) -> Self {
+        Affine::new(1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0)
+    }
+
+    /// A skew along the y axis by angle degrees.
+    pub fn skew_y(angle: f64) -> Self {
+        Affine::new(1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// This transform followed by other, equal to `other * self`.
+    pub fn then(self, other: Affine) -> Self {
+        other * self
+    }
+
+    /// Whether this transform leaves every point where it is.
+    pub fn is_identity(&self) -> bool {
+        self.approx_eq(&Affine::IDENTITY)
+    }
+
+    fn approx_eq(&self, other: &Affine) -> bool {
+        [
+            self.a - other.a,
+            self.b - other.b,
+            self.c - other.c,
+            self.d - other.d,
+            self.e - other.e,
+            self.f - other.f,
+        ]
+        .iter()
+        .all(|d| d.abs() < AFFINE_EPSILON)
+    }
+
+    /// The determinant of the linear part, the factor by which areas are scaled.
+    pub fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// The inverse transform, none if this transform collapses the plane onto a line or point.
+    pub fn invert(&self) -> Option<Affine> {
+        let det = self.determinant();
+        if det.abs() < f64::EPSILON || !det.is_finite() {
+            return None;
+        }
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Affine::new(
+            a,
+            b,
+            c,
+            d,
+            -(a * self.e + c * self.f),
+            -(b * self.e + d * self.f),
+        ))
+    }
+
+    /// Apply the transform to a point.
+    pub fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    /// The axis aligned bounds of a transformed box, given by its min and max corners.
+    pub fn apply_bounds(&self, min: (f64, f64), max: (f64, f64)) -> ((f64, f64), (f64, f64)) {
+        let corners = [min, (max.0, min.1), max, (min.0, max.1)].map(|p| self.apply(p));
+        corners.iter().skip(1).fold(
+            (corners[0], corners[0]),
+            |((min_x, min_y), (max_x, max_y)), (x, y)| {
+                (
+                    (min_x.min(*x), min_y.min(*y)),
+                    (max_x.max(*x), max_y.max(*y)),
+                )
+            },
+        )
+    }
+
+    /// Split this transform into translation, rotation, skew and scale.
+    pub fn decompose(&self) -> Decomposed {
+        let sx = self.a.hypot(self.b);
+        if sx < f64::EPSILON {
+            // Everything collapses onto the y axis, there is no meaningful rotation.
+            return Decomposed {
+                translate: (self.e, self.f),
+                rotation: 0.0,
+                skew_x: 0.0,
+                scale: (0.0, self.c.hypot(self.d)),
+            };
+        }
+        let sy = self.determinant() / sx;
+        let skew = if sy.abs() < f64::EPSILON {
+            0.0
+        } else {
+            ((self.a * self.c + self.b * self.d) / (sx * sy)).atan()
+        };
+        Decomposed {
+            translate: (self.e, self.f),
+            rotation: self.b.atan2(self.a).to_degrees(),
+            skew_x: skew.to_degrees(),
+            scale: (sx, sy),
+        }
+    }
+
+    /// Parse an svg transform list, like `translate(10,20) rotate(45)`.
+    pub fn parse(s: &str) -> Result<Affine, ParseTransformError> {
+        let err = || ParseTransformError(s.to_owned());
+        let mut result = Affine::IDENTITY;
+        let mut rest = s.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        while !rest.is_empty() {
+            let (name, after) = rest.split_once('(').ok_or_else(err)?;
+            let (args, after) = after.split_once(')').ok_or_else(err)?;
+            let args = args
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|a| !a.is_empty())
+                .map(|a| a.parse::<f64>().map_err(|_| err()))
+                .collect::<Result<Vec<f64>, _>>()?;
+            let transform = match (name.trim(), &args[..]) {
+                ("matrix", &[a, b, c, d, e, f]) => Affine::new(a, b, c, d, e, f),
+                ("translate", &[x]) => Affine::translate(x, 0.0),
+                ("translate", &[x, y]) => Affine::translate(x, y),
+                ("scale", &[s]) => Affine::scale(s, s),
+                ("scale", &[x, y]) => Affine::scale(x, y),
+                ("rotate", &[angle]) => Affine::rotate(angle),
+                ("rotate", &[angle, cx, cy]) => Affine::rotate_about(angle, cx, cy),
+                ("skewX", &[angle]) => Affine::skew_x(angle),
+                ("skewY", &[angle]) => Affine::skew_y(angle),
+                _ => return Err(err()),
+            };
+            result = result * transform;
+            rest = after.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        }
+        Ok(result)
+    }
+
+    /// Candidate svg forms of this transform, the matrix form is always valid.
+    fn forms(&self) -> Vec<String> {
+        let n = format_number;
+        let mut forms = vec![format!(
+            "matrix({},{},{},{},{},{})",
+            n(self.a),
+            n(self.b),
+            n(self.c),
+            n(self.d),
+            n(self.e),
+            n(self.f)
+        )];
+        let translated = self.e.abs() >= AFFINE_EPSILON || self.f.abs() >= AFFINE_EPSILON;
+        let translate = if self.f.abs() < AFFINE_EPSILON {
+            format!("translate({})", n(self.e))
+        } else {
+            format!("translate({},{})", n(self.e), n(self.f))
+        };
+        let with_translate = |form: String| {
+            if translated {
+                format!("{} {}", translate, form)
+            } else {
+                form
+            }
+        };
+        let linear = Affine {
+            e: 0.0,
+            f: 0.0,
+            ..*self
+        };
+
+        if linear.is_identity() {
+            forms.push(translate.clone());
+        }
+        if self.b.abs() < AFFINE_EPSILON && self.c.abs() < AFFINE_EPSILON {
+            // scale(x) is shorthand for scale(x,x), not scale(x,1).
+            let scale = if (self.a - self.d).abs() < AFFINE_EPSILON {
+                format!("scale({})", n(self.a))
+            } else {
+                format!("scale({},{})", n(self.a), n(self.d))
+            };
+            forms.push(with_translate(scale));
+        }
+        if (self.a - 1.0).abs() < AFFINE_EPSILON && (self.d - 1.0).abs() < AFFINE_EPSILON {
+            if self.b.abs() < AFFINE_EPSILON {
+                forms.push(with_translate(format!(
+                    "skewX({})",
+                    n(self.c.atan().to_degrees())
+                )));
+            }
+            if self.c.abs() < AFFINE_EPSILON {
+                forms.push(with_translate(format!(
+                    "skewY({})",
+                    n(self.b.atan().to_degrees())
+                )));
+            }
+        }
+        let rotation = self.b.atan2(self.a).to_degrees();
+        if linear.approx_eq(&Affine::rotate(rotation)) {
+            forms.push(with_translate(format!("rotate({})", n(rotation))));
+            if translated {
+                // A rotation about a point p moves p to itself, so p solves (I - R) p = t.
+                if let Some(inverse) = (Affine::IDENTITY.sub_linear(&linear)).invert() {
+                    let (cx, cy) = inverse.apply((self.e, self.f));
+                    forms.push(format!("rotate({},{},{})", n(rotation), n(cx), n(cy)));
+                }
+            }
+        }
+        forms
+    }
+
+    /// The linear part of self minus the linear part of other.
+    fn sub_linear(&self, other: &Affine) -> Affine {
+        Affine::new(
+            self.a - other.a,
+            self.b - other.b,
+            self.c - other.c,
+            self.d - other.d,
+            0.0,
+            0.0,
+        )
+    }
+}
+
+impl std::ops::Mul for Affine {
+    type Output = Affine;
+    fn mul(self, rhs: Affine) -> Affine {
+        Affine::new(
+            self.a * rhs.a + self.c * rhs.b,
+            self.b * rhs.a + self.d * rhs.b,
+            self.a * rhs.c + self.c * rhs.d,
+            self.b * rhs.c + self.d * rhs.d,
+            self.a * rhs.e + self.c * rhs.f + self.e,
+            self.b * rhs.e + self.d * rhs.f + self.f,
+        )
+    }
+}
+
+impl std::fmt::Display for Affine {
+    /// Formats as the shortest equivalent transform list, the identity is an empty list.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_identity() {
+            return Ok(());
+        }
+        let forms = self.forms();
+        let shortest = forms.iter().min_by_key(|s| s.len()).unwrap();
+        write!(f, "{}", shortest)
+    }
+}
+
+impl std::str::FromStr for Affine {
+    type Err = ParseTransformError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Affine::parse(s)
+    }
+}
+
+impl From<Affine> for svg::node::Value {
+    fn from(val: Affine) -> Self {
+        val.to_string().into()
+    }
+}
@@ -0,0 +1,118 @@
+use svg::node::element::{Circle, Group, Path, Rectangle};
+use svg::Node;
+use svg_util::bbox::{bounds, Rect};
+
+fn assert_bounds(node: &dyn Node, min: (f64, f64), max: (f64, f64)) {
+    let rect = bounds(node).expect("node should have bounds");
+    let expected = Rect::new(min, max);
+    for (a, b) in [
+        (rect.min_x, expected.min_x),
+        (rect.min_y, expected.min_y),
+        (rect.max_x, expected.max_x),
+        (rect.max_y, expected.max_y),
+    ] {
+        assert!((a - b).abs() < 1e-6, "{rect:?} vs {expected:?}");
+    }
+}
+
+fn path(d: &str) -> Path {
+    Path::new().set("d", d)
+}
+
+#[test]
+fn bbox_lines() {
+    assert_bounds(&path("M10 20 L30 -5 H40 v10 Z"), (10.0, -5.0), (40.0, 20.0));
+    assert_bounds(&path("m10 20 l5 5 l5 -30"), (10.0, -5.0), (20.0, 25.0));
+}
+
+#[test]
+fn bbox_curve_extremes() {
+    // The curve bulges beyond its end points, but not as far as its control points.
+    assert_bounds(&path("M0 0 C0 20 20 20 20 0"), (0.0, 0.0), (20.0, 15.0));
+    assert_bounds(&path("M0 0 Q10 20 20 0"), (0.0, 0.0), (20.0, 10.0));
+}
+
+#[test]
+fn bbox_smooth_curves() {
+    // After a cubic, a smooth cubic reflects its last control point.
+    assert_bounds(
+        &path("M0 0 C0 10 10 10 10 0 S20 -10 20 0"),
+        (0.0, -7.5),
+        (20.0, 7.5),
+    );
+    // After a quadratic, the first control point of a smooth cubic is the current point.
+    assert_bounds(
+        &path("M0 0 Q10 20 20 0 S30 0 40 0"),
+        (0.0, 0.0),
+        (40.0, 10.0),
+    );
+    // And after a cubic, the control point of a smooth quadratic is the current point.
+    assert_bounds(
+        &path("M0 0 C0 20 20 20 20 0 T40 0"),
+        (0.0, 0.0),
+        (40.0, 15.0),
+    );
+    // After a quadratic, a smooth quadratic reflects its control point.
+    assert_bounds(&path("M0 0 Q10 20 20 0 T40 0"), (0.0, -10.0), (40.0, 10.0));
+}
+
+#[test]
+fn bbox_arcs() {
+    // A half circle from left to right, clockwise on screen, passes through the top.
+    assert_bounds(&path("M0 0 A10 10 0 0 1 20 0"), (0.0, -10.0), (20.0, 0.0));
+    assert_bounds(&path("M0 0 A10 10 0 0 0 20 0"), (0.0, 0.0), (20.0, 10.0));
+    // A radius too small for the end points is scaled up until the arc fits.
+    assert_bounds(&path("M0 0 A1 1 0 0 1 20 0"), (0.0, -10.0), (20.0, 0.0));
+    assert_bounds(
+        &Circle::new().set("cx", 5).set("cy", 5).set("r", 5),
+        (0.0, 0.0),
+        (10.0, 10.0),
+    );
+}
+
+#[test]
+fn bbox_transformed_groups() {
+    let rect = || Rectangle::new().set("width", 10).set("height", 5);
+    assert_bounds(
+        &Group::new()
+            .set("transform", "translate(10,5) scale(2)")
+            .add(rect()),
+        (10.0, 5.0),
+        (30.0, 15.0),
+    );
+    // Nested transforms apply from the innermost outwards.
+    assert_bounds(
+        &Group::new()
+            .set("transform", "translate(100,0)")
+            .add(Group::new().set("transform", "rotate(90)").add(rect())),
+        (95.0, 0.0),
+        (100.0, 10.0),
+    );
+    // The bounds of a rotated circle are those of the circle, not of its rotated box.
+    assert_bounds(
+        &Group::new()
+            .set("transform", "rotate(45)")
+            .add(Circle::new().set("r", 10)),
+        (-10.0, -10.0),
+        (10.0, 10.0),
+    );
+}
+
+#[test]
+fn bbox_malformed_transform_is_skipped() {
+    let rect = || Rectangle::new().set("width", 10).set("height", 5);
+    assert!(bounds(&rect().set("transform", "translate(10,5")).is_none());
+    // Including the children of a group with such a transform.
+    assert_bounds(
+        &Group::new()
+            .add(rect())
+            .add(rect().set("x", 100).set("transform", "scale(2) junk"))
+            .add(
+                Group::new()
+                    .set("transform", "rotate(")
+                    .add(Circle::new().set("r", 50)),
+            ),
+        (0.0, 0.0),
+        (10.0, 5.0),
+    );
+}
@@ -0,0 +1,74 @@
+use svg_util::color::Color;
+
+#[test]
+fn color_parse_hex() {
+    assert_eq!(Color::parse("#1E88E5"), Ok(Color::from_hex(0x1E88E5)));
+    assert_eq!(Color::parse("#1e88e5"), Ok(Color::from_hex(0x1E88E5)));
+    assert_eq!(Color::parse("#F00"), Ok(Color::rgb(255, 0, 0)));
+    assert_eq!(Color::parse("#FF000080").unwrap().a, 128.0 / 255.0);
+    assert_eq!(Color::parse("#F008").unwrap().a, 136.0 / 255.0);
+    assert!(Color::parse("#12345").is_err());
+    assert!(Color::parse("#GGGGGG").is_err());
+}
+
+#[test]
+fn color_parse_rgb() {
+    assert_eq!(
+        Color::parse("rgb(30, 136, 229)"),
+        Ok(Color::rgb(30, 136, 229))
+    );
+    assert_eq!(
+        Color::parse("rgb(100%, 0%, 50%)"),
+        Ok(Color::rgb(255, 0, 128))
+    );
+    assert_eq!(
+        Color::parse("rgba(255, 0, 0, 0.5)"),
+        Ok(Color::rgba(255, 0, 0, 0.5))
+    );
+    assert_eq!(
+        Color::parse("rgb(255 0 0 / 50%)"),
+        Ok(Color::rgba(255, 0, 0, 0.5))
+    );
+    assert!(Color::parse("rgb(1, 2)").is_err());
+    assert!(Color::parse("rgb(a, b, c)").is_err());
+}
+
+#[test]
+fn color_parse_hsl() {
+    assert_eq!(Color::parse("hsl(0, 100%, 50%)"), Ok(Color::rgb(255, 0, 0)));
+    assert_eq!(
+        Color::parse("hsl(120deg, 100%, 25%)"),
+        Ok(Color::rgb(0, 128, 0))
+    );
+    assert_eq!(
+        Color::parse("hsla(240, 100%, 50%, 0.25)"),
+        Ok(Color::rgba(0, 0, 255, 0.25))
+    );
+    assert!(Color::parse("hsl(0, 100%)").is_err());
+}
+
+#[test]
+fn color_parse_named() {
+    assert_eq!(Color::parse("white"), Ok(Color::WHITE));
+    assert_eq!(Color::parse(" SteelBlue "), Ok(Color::from_hex(0x4682B4)));
+    assert_eq!(Color::parse("transparent").unwrap().a, 0.0);
+    assert!(Color::parse("notacolor").is_err());
+}
+
+#[test]
+fn color_try_from_str() {
+    assert_eq!(Color::try_from("red"), Ok(Color::rgb(255, 0, 0)));
+    assert_eq!("#00F".parse::<Color>(), Ok(Color::rgb(0, 0, 255)));
+    // Typos are reported, instead of becoming some color.
+    for typo in ["#ff00gg", "blu"] {
+        let err = Color::try_from(typo).unwrap_err();
+        assert_eq!(err.to_string(), format!("invalid color {typo:?}"));
+    }
+}
+
+#[test]
+fn color_display_round_trip() {
+    for color in [Color::from_hex(0x1E88E5), Color::rgba(10, 20, 30, 0.5)] {
+        assert_eq!(Color::parse(&color.to_string()), Ok(color));
+    }
+}
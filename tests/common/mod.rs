@@ -0,0 +1,49 @@
+//! Helpers for inspecting rendered svg, shared by the integration tests.
+#![allow(dead_code)]
+
+use svg::Node;
+
+/// The node and all of its descendants, in document order.
+pub fn descendants(node: &dyn Node) -> Vec<&dyn Node> {
+    let mut out = vec![node];
+    for child in node.get_children().into_iter().flatten() {
+        out.extend(descendants(child.as_ref()));
+    }
+    out
+}
+
+/// The value of an attribute of the node.
+pub fn attribute(node: &dyn Node, name: &str) -> Option<String> {
+    node.get_attributes()?.get(name).map(|v| v.to_string())
+}
+
+/// Whether the node is a text element, as opposed to the text node inside it.
+pub fn is_text(node: &dyn Node) -> bool {
+    node.get_name() == "text" && !node.is_bare()
+}
+
+/// The content of a text element.
+pub fn text(node: &dyn Node) -> String {
+    node.get_children()
+        .into_iter()
+        .flatten()
+        .map(|c| c.to_string())
+        .collect()
+}
+
+/// The content of every text element in the node, in document order.
+pub fn texts(node: &dyn Node) -> Vec<String> {
+    descendants(node)
+        .into_iter()
+        .filter(|n| is_text(*n))
+        .map(text)
+        .collect()
+}
+
+/// The elements with the provided name in the node, in document order.
+pub fn elements<'a>(node: &'a dyn Node, name: &str) -> Vec<&'a dyn Node> {
+    descendants(node)
+        .into_iter()
+        .filter(|n| n.get_name() == name && !n.is_bare())
+        .collect()
+}
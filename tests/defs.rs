@@ -0,0 +1,140 @@
+mod common;
+
+use svg::node::element::LinearGradient;
+use svg::node::Text;
+use svg_util::color::Color;
+use svg_util::defs::{Defs, DefsError, Gradient, Pattern};
+use svg_util::document::Canvas;
+use svg_util::pie_chart::{PieChart, PieSegment};
+use svg_util::plot;
+
+#[test]
+fn defs_identical_definitions_share_an_id() {
+    let mut defs = Defs::with_prefix("d");
+    let gradient = Gradient::linear(0.0).stops(&[Color::WHITE, Color::BLACK]);
+    let a = defs.add(gradient.svg()).unwrap();
+    let b = defs.add(gradient.svg()).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(defs.len(), 1);
+}
+
+#[test]
+fn defs_ids_follow_content() {
+    let white = Gradient::linear(0.0).stops(&[Color::WHITE, Color::BLACK]);
+    let black = Gradient::linear(0.0).stops(&[Color::BLACK, Color::WHITE]);
+    let a = Defs::new().add(white.svg()).unwrap();
+    assert_eq!(Defs::new().add(white.svg()).unwrap(), a);
+    assert_ne!(Defs::new().add(black.svg()).unwrap(), a);
+    assert!(a.starts_with("def-"), "{a}");
+}
+
+#[test]
+fn defs_assigned_ids_skip_explicit_ids() {
+    let gradient = || {
+        Gradient::linear(0.0)
+            .stops(&[Color::WHITE, Color::BLACK])
+            .svg()
+    };
+    let derived = Defs::with_prefix("d").add(gradient()).unwrap();
+
+    let mut defs = Defs::with_prefix("d");
+    let explicit = defs
+        .add(LinearGradient::new().set("id", derived.clone()))
+        .unwrap();
+    assert_eq!(explicit, derived);
+    let assigned = defs.add(gradient()).unwrap();
+    assert_eq!(assigned, format!("{derived}-1"));
+
+    // An explicit id matching an assigned one is an error, instead of a broken reference.
+    let duplicate = LinearGradient::new().set("id", assigned.clone());
+    assert_eq!(defs.add(duplicate), Err(DefsError::DuplicateId(assigned)));
+}
+
+#[test]
+fn defs_bare_node() {
+    let mut defs = Defs::new();
+    assert_eq!(
+        defs.add(Text::new("not a definition")),
+        Err(DefsError::Bare)
+    );
+    assert!(defs.is_empty());
+}
+
+/// Every definition in the node by its id, and every id referenced with url().
+fn ids(node: &dyn svg::Node, defined: &mut Vec<(String, String)>, referenced: &mut Vec<String>) {
+    for node in common::descendants(node) {
+        for (name, value) in node.get_attributes().into_iter().flatten() {
+            let value = value.to_string();
+            if name == "id" {
+                defined.push((value, node.to_string()));
+            } else if let Some(id) = value.strip_prefix("url(#") {
+                referenced.push(id.trim_end_matches(')').to_owned());
+            }
+        }
+    }
+}
+
+fn patterned_pie_chart(pattern: Pattern) -> PieChart {
+    let mut chart = PieChart::new();
+    chart.set_segments(&[PieSegment::new(0.5).fill(pattern), PieSegment::new(0.5)]);
+    chart
+}
+
+#[test]
+fn defs_segment_fill() {
+    let chart = patterned_pie_chart(Pattern::diagonal());
+    let (mut defined, mut referenced) = (vec![], vec![]);
+    ids(&chart.svg(), &mut defined, &mut referenced);
+    assert_eq!(defined.len(), 1);
+    assert_eq!(referenced, [defined[0].0.clone()]);
+    assert!(defined[0].1.starts_with("<pattern"), "{}", defined[0].1);
+}
+
+#[test]
+fn defs_rendering_is_reproducible() {
+    let chart = patterned_pie_chart(Pattern::dots());
+    assert_eq!(chart.svg().to_string(), chart.svg().to_string());
+    let canvas = || {
+        Canvas::new()
+            .add(chart.svg())
+            .background(Pattern::diagonal())
+    };
+    assert_eq!(canvas().svg().to_string(), canvas().svg().to_string());
+}
+
+#[test]
+fn defs_charts_in_one_document() {
+    let first = patterned_pie_chart(Pattern::diagonal());
+    let second = patterned_pie_chart(Pattern::dots());
+
+    let v = plot::AxisVertical::new(100.0);
+    let h = plot::AxisHorizontal::new(100.0);
+    h.set_categories(&["A", "B"]);
+    let mut p = plot::Plot::new(&v.combine(&h));
+    p.bar(&[1.0, 2.0]).set_fill(Pattern::cross_hatch());
+
+    // Charts rendered on their own, and charts sharing a registry with their legend.
+    let mut defs = Defs::new();
+    let legend = second.legend_with_defs(&["A", "B"], &mut defs);
+    let canvas = Canvas::new()
+        .background(Gradient::linear(0.0).stops(&[Color::WHITE, Color::BLACK]))
+        .add(first.svg())
+        .add(p.svg())
+        .add(second.svg_with_defs(&mut defs))
+        .add(legend)
+        .add(p.svg_with_defs(&mut defs))
+        .add(defs);
+
+    let (mut defined, mut referenced) = (vec![], vec![]);
+    ids(&canvas.svg(), &mut defined, &mut referenced);
+    assert_eq!(defined.len(), 5, "{defined:?}");
+    // An id defined more than once always stands for the same definition.
+    for (i, (id, definition)) in defined.iter().enumerate() {
+        for (other, other_definition) in &defined[i + 1..] {
+            assert!(id != other || definition == other_definition, "{id}");
+        }
+    }
+    for id in referenced {
+        assert!(defined.iter().any(|(d, _)| *d == id), "{id} in {defined:?}");
+    }
+}
@@ -0,0 +1,32 @@
+use svg::node::element::Rectangle;
+use svg::Node;
+use svg_util::document::{Canvas, Unit};
+
+fn canvas() -> Canvas {
+    Canvas::new().add(Rectangle::new().set("width", 200).set("height", 100))
+}
+
+#[test]
+fn document_size_last_of_width_and_scale() {
+    assert_eq!(canvas().scale(2.0).size(), (400.0, 200.0));
+    assert_eq!(canvas().width(50.0).size(), (50.0, 25.0));
+    assert_eq!(canvas().scale(2.0).width(50.0).size(), (50.0, 25.0));
+    assert_eq!(canvas().width(50.0).scale(2.0).size(), (400.0, 200.0));
+}
+
+#[test]
+fn document_attributes() {
+    let document = canvas().margin(10.0).unit(Unit::In).dpi(100.0).svg();
+    let attributes = document.get_attributes().unwrap();
+    assert_eq!(attributes["viewBox"].to_string(), "-10 -10 220 120");
+    assert_eq!(attributes["width"].to_string(), "2.2in");
+    assert_eq!(attributes["height"].to_string(), "1.2in");
+}
+
+#[test]
+fn document_empty_canvas() {
+    let view_box = |canvas: Canvas| canvas.svg().get_attributes().unwrap()["viewBox"].to_string();
+    assert_eq!(view_box(Canvas::new()), "0 0 1 1");
+    assert_eq!(view_box(Canvas::new().margin(5.0)), "-5 -5 10 10");
+    assert_eq!(Canvas::new().scale(2.0).size(), (2.0, 2.0));
+}
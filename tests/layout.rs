@@ -0,0 +1,102 @@
+use svg::node::element::{Group, Rectangle};
+use svg::Node;
+use svg_util::bbox::bounds;
+use svg_util::layout::{Align, Flow, Grid, Stack};
+
+fn rect(width: f64, height: f64) -> Rectangle {
+    Rectangle::new().set("width", width).set("height", height)
+}
+
+/// The top left corner of each child as placed, and the size of the whole layout.
+fn placed(group: Group, padding: bool) -> (Vec<(f64, f64)>, (f64, f64)) {
+    let size = bounds(&group).map_or((0.0, 0.0), |r| (r.width(), r.height()));
+    let corners = group
+        .get_children()
+        .unwrap()
+        .iter()
+        // The padding is an invisible rectangle before the children.
+        .skip(padding as usize)
+        .map(|child| {
+            let r = bounds(child.as_ref()).unwrap();
+            (r.min_x, r.min_y)
+        })
+        .collect();
+    (corners, size)
+}
+
+#[test]
+fn layout_stack_gap_and_align() {
+    let stack = Stack::horizontal()
+        .add(rect(10.0, 20.0))
+        .add(rect(30.0, 10.0))
+        .gap(5.0)
+        .align(Align::Center);
+    assert_eq!(
+        placed(stack.svg(), false),
+        (vec![(0.0, 0.0), (15.0, 5.0)], (45.0, 20.0))
+    );
+
+    let stack = Stack::vertical()
+        .add(rect(10.0, 20.0))
+        .add(rect(30.0, 10.0))
+        .gap(5.0)
+        .align(Align::End);
+    assert_eq!(
+        placed(stack.svg(), false),
+        (vec![(20.0, 0.0), (0.0, 25.0)], (30.0, 35.0))
+    );
+}
+
+#[test]
+fn layout_stack_padding() {
+    let stack = Stack::horizontal()
+        .add(rect(10.0, 20.0))
+        .add(rect(30.0, 10.0))
+        .padding(4.0);
+    assert_eq!(
+        placed(stack.svg(), true),
+        (vec![(4.0, 4.0), (14.0, 4.0)], (48.0, 28.0))
+    );
+    // Without children only the padding remains.
+    assert_eq!(
+        placed(Stack::vertical().padding(4.0).svg(), true),
+        (vec![], (8.0, 8.0))
+    );
+}
+
+#[test]
+fn layout_grid() {
+    let grid = Grid::new(2)
+        .add(rect(10.0, 10.0))
+        .add(rect(20.0, 5.0))
+        .add(rect(5.0, 15.0))
+        .gaps(2.0, 3.0)
+        .padding(1.0)
+        .align(Align::End, Align::Center);
+    assert_eq!(grid.rows(), 2);
+    // Columns are 10 and 20 wide, rows are 10 and 15 high.
+    assert_eq!(
+        placed(grid.svg(), true),
+        (vec![(1.0, 1.0), (13.0, 3.5), (6.0, 14.0)], (34.0, 30.0))
+    );
+}
+
+#[test]
+fn layout_flow_wraps() {
+    let flow = Flow::new(30.0)
+        .add(rect(10.0, 10.0))
+        .add(rect(15.0, 20.0))
+        .add(rect(10.0, 5.0))
+        .add(rect(40.0, 5.0))
+        .gap(2.0)
+        .line_gap(4.0)
+        .align(Align::End);
+    // The third child doesn't fit on the first line, the fourth is wider than the flow.
+    assert_eq!(
+        placed(flow.svg(), false),
+        (
+            vec![(0.0, 10.0), (12.0, 0.0), (0.0, 24.0), (0.0, 33.0)],
+            (40.0, 38.0)
+        )
+    );
+}
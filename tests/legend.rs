@@ -0,0 +1,101 @@
+mod common;
+
+use svg::Node;
+use svg_util::legend::{Legend, LegendEntry, LegendOrientation};
+
+/// The x and y of each label in the legend.
+fn label_positions(legend: &Legend) -> Vec<(String, String)> {
+    let group = legend.svg();
+    common::descendants(&group)
+        .into_iter()
+        .filter(|n| common::is_text(*n))
+        .map(|n| {
+            (
+                common::attribute(n, "x").unwrap(),
+                common::attribute(n, "y").unwrap(),
+            )
+        })
+        .collect()
+}
+
+fn three_entries(orientation: LegendOrientation) -> Legend {
+    Legend::new()
+        .entry(LegendEntry::new("A"))
+        .entry(LegendEntry::new("B"))
+        .entry(LegendEntry::new("C"))
+        .orientation(orientation)
+        .sized(10.0, 4.0)
+        .font_size(10.0)
+        .entry_width(50.0)
+        .wrap(2)
+}
+
+#[test]
+fn legend_wraps_into_columns() {
+    // Rows are 14 apart, columns are the entry width plus twice the spacing apart.
+    assert_eq!(
+        label_positions(&three_entries(LegendOrientation::Vertical)),
+        [("14", "5"), ("14", "19"), ("72", "5")].map(|(x, y)| (x.to_owned(), y.to_owned()))
+    );
+}
+
+#[test]
+fn legend_wraps_into_rows() {
+    assert_eq!(
+        label_positions(&three_entries(LegendOrientation::Horizontal)),
+        [("14", "5"), ("72", "5"), ("14", "19")].map(|(x, y)| (x.to_owned(), y.to_owned()))
+    );
+}
+
+#[test]
+fn legend_from_plot_series() {
+    use svg_util::plot::{AxisHorizontal, AxisVertical, Plot};
+    let h = AxisHorizontal::new(100.0);
+    let v = AxisVertical::new(100.0);
+    let mut plot = Plot::new(&v.combine(&h));
+    plot.line_xy(&[(0.0, 0.0), (1.0, 1.0)])
+        .set_label("line")
+        .set("stroke", "red");
+    plot.line_xy(&[(0.0, 1.0), (1.0, 0.0)]);
+    plot.scatter_xy(&[(0.5, 0.5)])
+        .set_label("points")
+        .set("fill", "blue");
+
+    // Only labeled series get an entry, with a swatch styled like the series.
+    let group = plot.legend().svg();
+    assert_eq!(common::texts(&group), ["line", "points"]);
+    let entries = group.get_children().unwrap();
+    assert_eq!(entries.len(), 2);
+    let swatches: Vec<&dyn Node> = entries
+        .iter()
+        .map(|e| &*e.get_children().unwrap()[0])
+        .collect();
+    assert_eq!(swatches[0].get_name(), "line");
+    assert_eq!(
+        common::attribute(swatches[0], "stroke").as_deref(),
+        Some("red")
+    );
+    assert_eq!(swatches[1].get_name(), "circle");
+    assert_eq!(
+        common::attribute(swatches[1], "fill").as_deref(),
+        Some("blue")
+    );
+}
+
+#[test]
+fn legend_from_pie_segments() {
+    use svg_util::pie_chart::PieChart;
+    let mut chart = PieChart::new();
+    chart.set_segments(&[0.5, 0.3, 0.2]);
+
+    // One entry per segment, filled like the segment.
+    let group = chart.legend(&["A", "B", "C"]).svg();
+    assert_eq!(common::texts(&group), ["A", "B", "C"]);
+    let fills = |node: &dyn Node, name: &str| -> Vec<String> {
+        common::elements(node, name)
+            .into_iter()
+            .map(|n| common::attribute(n, "fill").unwrap())
+            .collect()
+    };
+    assert_eq!(fills(&group, "rect"), fills(&chart.svg(), "path"));
+}
@@ -0,0 +1,55 @@
+use svg_util::palette::Palette;
+
+#[test]
+fn palette_default_distinct() {
+    let colors = Palette::default().colors(10);
+    for (i, a) in colors.iter().enumerate() {
+        for b in &colors[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+    // The eleventh item repeats the first color.
+    assert_eq!(Palette::default().color(10, 11), colors[0]);
+}
+
+/// The red, green and blue components of a #RRGGBB color.
+fn channels(color: &str) -> [u8; 3] {
+    let hex = u32::from_str_radix(color.trim_start_matches('#'), 16).unwrap();
+    [(hex >> 16) as u8, (hex >> 8) as u8, hex as u8]
+}
+
+/// Sample the palette from t0 to t1 and check every channel moves in one direction.
+fn assert_monotonic(palette: &Palette, t0: f64, t1: f64, increasing: bool) {
+    let samples: Vec<[u8; 3]> = (0..=20)
+        .map(|i| channels(&palette.sample(t0 + (t1 - t0) * i as f64 / 20.0).to_string()))
+        .collect();
+    for pair in samples.windows(2) {
+        for (a, b) in pair[0].iter().zip(pair[1]) {
+            assert!(if increasing { *a <= b } else { *a >= b }, "{samples:?}");
+        }
+    }
+}
+
+#[test]
+fn palette_sequential_sample() {
+    let blues = Palette::blues();
+    assert_eq!(blues.sample(0.0).to_string(), "#F7FBFF");
+    assert_eq!(blues.sample(1.0).to_string(), "#08306B");
+    // Out of range positions are clamped to the ends.
+    assert_eq!(blues.sample(-1.0).to_string(), "#F7FBFF");
+    assert_eq!(blues.sample(2.0).to_string(), "#08306B");
+    assert_monotonic(&blues, 0.0, 1.0, false);
+}
+
+#[test]
+fn palette_diverging_sample() {
+    let red_blue = Palette::red_blue();
+    assert_eq!(red_blue.sample(0.0).to_string(), "#B2182B");
+    assert_eq!(red_blue.sample(0.5).to_string(), "#F7F7F7");
+    assert_eq!(red_blue.sample(1.0).to_string(), "#2166AC");
+    // Towards the neutral center from either end.
+    assert_monotonic(&red_blue, 0.0, 0.5, true);
+    assert_monotonic(&red_blue, 0.5, 1.0, false);
+    // A single item takes the neutral color.
+    assert_eq!(red_blue.color(0, 1).to_string(), "#F7F7F7");
+}
@@ -0,0 +1,579 @@
+mod common;
+
+use std::f64::consts::PI;
+use svg::node::element::path::{Command, Data, Position};
+use svg::Node;
+use svg_util::pie_chart::{PieChart, StartStyle};
+
+/// An arc found in path data, with absolute start and end points.
+#[derive(Debug)]
+struct Arc {
+    radius: f64,
+    large_arc: bool,
+    sweep: bool,
+    start: (f64, f64),
+    end: (f64, f64),
+}
+
+impl Arc {
+    /// The angle swept by this arc around the origin, in the direction of the sweep flag.
+    fn swept(&self) -> f64 {
+        let a = self.start.1.atan2(self.start.0);
+        let b = self.end.1.atan2(self.end.0);
+        let d = if self.sweep { b - a } else { a - b };
+        d.rem_euclid(2.0 * PI)
+    }
+}
+
+/// The path data of all paths in the chart, one entry per segment.
+fn segment_paths(chart: &PieChart) -> Vec<Data> {
+    let group = chart.svg();
+    group
+        .get_children()
+        .unwrap()
+        .iter()
+        .filter_map(|c| c.get_attributes().and_then(|a| a.get("d")))
+        .map(|d| Data::parse(d).expect("path data should parse"))
+        .collect()
+}
+
+/// Walk the path data and collect the arcs with their absolute positions.
+fn arcs(data: &Data) -> Vec<Arc> {
+    let mut arcs = vec![];
+    let mut current = (0.0, 0.0);
+    let mut subpath_start = (0.0, 0.0);
+    for command in data.iter() {
+        let point = |p: &[f32], position: &Position, current: (f64, f64)| {
+            let (x, y) = (p[0] as f64, p[1] as f64);
+            match position {
+                Position::Absolute => (x, y),
+                Position::Relative => (current.0 + x, current.1 + y),
+            }
+        };
+        match command {
+            Command::Move(position, p) => {
+                current = point(&p[0..2], position, current);
+                subpath_start = current;
+            }
+            Command::Line(position, p) => {
+                current = point(&p[0..2], position, current);
+            }
+            Command::EllipticalArc(position, p) => {
+                let end = point(&p[5..7], position, current);
+                arcs.push(Arc {
+                    radius: p[0] as f64,
+                    large_arc: p[3] != 0.0,
+                    sweep: p[4] != 0.0,
+                    start: current,
+                    end,
+                });
+                current = end;
+            }
+            Command::Close => {
+                current = subpath_start;
+            }
+            _ => panic!("unexpected command {:?}", command),
+        }
+    }
+    arcs
+}
+
+#[test]
+fn pie_chart_donut_and_caption() {
+    use svg::node::element::Text;
+    let mut chart = PieChart::new();
+    chart.set_radius(100.0);
+    chart.set_inner_radius(40.0);
+    chart.set_segments(&[0.3, 0.3, 0.4]);
+    chart.set_center_caption(Text::new("Total").set("text-anchor", "middle"));
+
+    // Each segment runs clockwise along the outside and back along the hole.
+    let point = |angle: f64, r: f64| (angle.cos() * r, angle.sin() * r);
+    let close = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3;
+    let angles = [(0.0, 0.6 * PI), (0.6 * PI, 0.6 * PI), (1.2 * PI, 0.8 * PI)];
+    for (data, (start, angle)) in segment_paths(&chart).iter().zip(angles) {
+        let arcs = arcs(data);
+        assert_eq!(arcs.len(), 2);
+        let (outer, inner) = (&arcs[0], &arcs[1]);
+        assert_eq!((outer.radius, outer.sweep), (100.0, true));
+        assert_eq!((inner.radius, inner.sweep), (40.0, false));
+        assert!(close(outer.start, point(start, 100.0)));
+        assert!(close(outer.end, point(start + angle, 100.0)));
+        assert!(close(inner.start, point(start + angle, 40.0)));
+        assert!(close(inner.end, point(start, 40.0)));
+        assert!((inner.swept() - angle).abs() < 1e-3);
+        assert_eq!(inner.large_arc, angle > PI);
+    }
+
+    // The caption is drawn last, on top of the hole, as provided.
+    let group = chart.svg();
+    let caption = group.get_children().unwrap().last().unwrap();
+    assert_eq!(caption.get_name(), "text");
+    assert_eq!(common::text(&**caption), "Total");
+    assert_eq!(
+        common::attribute(&**caption, "text-anchor").as_deref(),
+        Some("middle")
+    );
+}
+
+/// Check the arcs of each segment against the ratios the chart was made from.
+fn check_chart(ratios: &[f64], radius: f64, inner_radius: f64, start: f64, style: StartStyle) {
+    let mut chart = PieChart::new();
+    chart.set_radius(radius);
+    chart.set_inner_radius(inner_radius);
+    chart.set_start(start, style);
+    chart.set_segments(ratios);
+
+    let paths = segment_paths(&chart);
+    assert_eq!(paths.len(), ratios.len());
+    for (ratio, data) in ratios.iter().zip(paths.iter()) {
+        let context = format!("ratio {ratio} in {ratios:?}, inner radius {inner_radius}");
+        let arcs = arcs(data);
+        let expected = ratio * 2.0 * PI;
+        let full = *ratio >= 1.0;
+        if expected < 1e-6 {
+            // Empty segments are degenerate, their arcs are irrelevant.
+            continue;
+        }
+        for (r, outer) in [(radius, true), (inner_radius, false)] {
+            let ring: Vec<&Arc> = arcs
+                .iter()
+                .filter(|a| (a.radius - r).abs() < 1e-6)
+                .collect();
+            if r == 0.0 {
+                assert!(ring.is_empty(), "{context}");
+                continue;
+            }
+            if full {
+                // A full circle needs two arcs, a single arc between identical points is invisible.
+                assert_eq!(ring.len(), 2, "{context}");
+                assert!(ring.iter().all(|a| a.sweep == outer), "{context}");
+            } else {
+                assert_eq!(ring.len(), 1, "{context}");
+            }
+            let mut total = 0.0;
+            for arc in ring {
+                // Endpoints must lie on the circle.
+                for (x, y) in [arc.start, arc.end] {
+                    assert!(((x * x + y * y).sqrt() - r).abs() < 1e-3, "{context}");
+                }
+                let swept = arc.swept();
+                // Exactly half a circle is ambiguous, either flag draws the same arc.
+                if (swept - PI).abs() > 1e-3 {
+                    assert_eq!(arc.large_arc, swept > PI, "{context}, {arc:?}");
+                }
+                if !full {
+                    assert_eq!(arc.sweep, outer, "{context}");
+                }
+                total += swept;
+            }
+            if !full {
+                assert!(
+                    (total - expected).abs() < 1e-3,
+                    "{context}: {total} vs {expected}"
+                );
+            } else {
+                assert!((total - 2.0 * PI).abs() < 1e-3, "{context}: {total}");
+            }
+        }
+    }
+}
+
+#[test]
+fn pie_chart_ratio_combinations() {
+    let values = [0.01, 0.1, 0.25, 0.4999, 0.5, 0.5001, 0.6, 0.75, 0.9, 0.99];
+    let starts = [
+        (0.0, StartStyle::Edge),
+        (-PI / 2.0, StartStyle::Edge),
+        (PI / 3.0, StartStyle::Center),
+        (PI / 4.0, StartStyle::CenterLargest),
+    ];
+    for (start, style) in starts {
+        for inner_radius in [0.0, 40.0] {
+            for a in values {
+                // A single segment, and the same segment with its complement.
+                check_chart(&[a], 100.0, inner_radius, start, style);
+                check_chart(&[a, 1.0 - a], 100.0, inner_radius, start, style);
+                for b in values {
+                    if a + b <= 1.0 {
+                        let c = (1.0 - a - b).max(0.0);
+                        check_chart(&[a, b, c], 100.0, inner_radius, start, style);
+                        check_chart(&[b, c, a], 100.0, inner_radius, start, style);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn pie_chart_full_segment() {
+    for inner_radius in [0.0, 25.0, 50.0] {
+        check_chart(&[1.0], 100.0, inner_radius, 0.0, StartStyle::Edge);
+        check_chart(&[1.0], 100.0, inner_radius, 1.0, StartStyle::Center);
+        check_chart(
+            &[0.0, 1.0, 0.0],
+            100.0,
+            inner_radius,
+            0.5,
+            StartStyle::CenterLargest,
+        );
+    }
+}
+
+#[test]
+fn pie_chart_large_arc_flag() {
+    let mut chart = PieChart::new();
+    chart.set_radius(10.0);
+    chart.set_segments(&[0.75, 0.25]);
+    let paths = segment_paths(&chart);
+    let large: Vec<Vec<bool>> = paths
+        .iter()
+        .map(|d| arcs(d).iter().map(|a| a.large_arc).collect())
+        .collect();
+    assert_eq!(large, vec![vec![true], vec![false]]);
+}
+
+#[test]
+fn pie_chart_segment_builders() {
+    use svg_util::pie_chart::PieSegment;
+    let segment = PieSegment::new(0.25).label("Rent");
+    assert_eq!(segment.ratio, 0.25);
+    assert_eq!(segment.label, "Rent");
+
+    let mut chart = PieChart::new();
+    chart.set_segments(&[segment, PieSegment::new(0.75)]);
+    assert_eq!(chart.segment_angles().len(), 2);
+}
+
+/// The text, x, y and text-anchor of every label in the chart.
+fn label_boxes(chart: &PieChart) -> Vec<(String, f64, f64, String)> {
+    let group = chart.svg();
+    common::elements(&group, "text")
+        .into_iter()
+        .map(|t| {
+            let number = |name| {
+                let v: f64 = common::attribute(t, name).unwrap().parse().unwrap();
+                (v * 1e6).round() / 1e6
+            };
+            (
+                common::text(t),
+                number("x"),
+                number("y"),
+                common::attribute(t, "text-anchor").unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn pie_chart_label_inside() {
+    let mut chart = PieChart::new();
+    chart.set_radius(100.0);
+    chart.set_segments(&[(0.5, "A"), (0.5, "B")]);
+    // Labels that fit are centered on the bisector, a bit beyond halfway the radius.
+    assert_eq!(
+        label_boxes(&chart),
+        [
+            ("A".to_owned(), 0.0, 65.0, "middle".to_owned()),
+            ("B".to_owned(), 0.0, -65.0, "middle".to_owned())
+        ]
+    );
+
+    // For donuts they're halfway the ring.
+    chart.set_inner_radius(60.0);
+    assert_eq!(label_boxes(&chart)[0].2, 80.0);
+    assert!(common::elements(&chart.svg(), "polyline").is_empty());
+}
+
+#[test]
+fn pie_chart_label_outside_with_leader() {
+    let mut chart = PieChart::new();
+    chart.set_radius(100.0);
+    chart.set_leader_length(10.0);
+    chart.set_segments(&[(0.02, "small"), (0.98, "")]);
+
+    let labels = label_boxes(&chart);
+    assert_eq!(labels.len(), 1);
+    let (text, x, _, anchor) = &labels[0];
+    assert_eq!((text.as_str(), anchor.as_str()), ("small", "start"));
+    // Beyond the chart and both leader segments.
+    assert!(*x > 120.0, "{x}");
+
+    let group = chart.svg();
+    let leaders = common::elements(&group, "polyline");
+    assert_eq!(leaders.len(), 1);
+    let points: Vec<(f64, f64)> = common::attribute(leaders[0], "points")
+        .unwrap()
+        .split(' ')
+        .map(|p| {
+            let (x, y) = p.split_once(',').unwrap();
+            (x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect();
+    // From the edge of the segment at its bisector, to the label.
+    let mid = 0.02 * PI;
+    assert!((points[0].0 - mid.cos() * 100.0).abs() < 1e-6);
+    assert!((points[0].1 - mid.sin() * 100.0).abs() < 1e-6);
+    assert_eq!(points[2].0, 120.0);
+    assert!((points[2].1 - labels[0].2).abs() < 1e-6);
+}
+
+#[test]
+fn pie_chart_outside_labels_do_not_overlap() {
+    let mut chart = PieChart::new();
+    chart.set_radius(100.0);
+    chart.set_label_font_size(10.0);
+    chart.set_segments(&[
+        (0.01, "a"),
+        (0.01, "b"),
+        (0.01, "c"),
+        (0.01, "d"),
+        (0.96, ""),
+    ]);
+
+    let labels = label_boxes(&chart);
+    assert_eq!(labels.len(), 4);
+    assert!(labels.iter().all(|l| l.3 == "start"));
+    let mut ys: Vec<f64> = labels.iter().map(|l| l.2).collect();
+    ys.sort_by(f64::total_cmp);
+    // At least a line height apart.
+    for pair in ys.windows(2) {
+        assert!(pair[1] - pair[0] >= 12.0 - 1e-6, "{ys:?}");
+    }
+}
+
+#[test]
+fn pie_chart_explode_along_bisector() {
+    use svg_util::pie_chart::PieSegment;
+    let segments = [PieSegment::new(0.25).explode(6.0), PieSegment::new(0.75)];
+    let mut flat = PieChart::new();
+    flat.set_radius(50.0);
+    flat.set_inner_radius(20.0);
+    flat.set_segments(&[0.25, 0.75]);
+    let mut chart = flat.clone();
+    chart.set_segments(&segments);
+
+    // Only the segment with its own distance moves, then the chart-wide distance applies to the rest.
+    for (explode, distances) in [(0.0, [6.0, 0.0]), (4.0, [6.0, 4.0])] {
+        chart.set_explode(explode);
+        let angles = chart.segment_angles();
+        let moved = segment_paths(&chart);
+        for (si, data) in segment_paths(&flat).iter().enumerate() {
+            let (start, angle) = angles[si];
+            let mid = start + angle / 2.0;
+            let expected = (mid.cos() * distances[si], mid.sin() * distances[si]);
+            for (a, b) in arcs(data).iter().zip(arcs(&moved[si])) {
+                let offset = (b.start.0 - a.start.0, b.start.1 - a.start.1);
+                assert!(
+                    (offset.0 - expected.0).abs() < 1e-3 && (offset.1 - expected.1).abs() < 1e-3,
+                    "segment {si}: {offset:?} vs {expected:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn pie_chart_from_values() {
+    use svg_util::pie_chart::PieChartError;
+    let mut chart = PieChart::from_values(&[("a", 30.0), ("b", 10.0), ("c", 0.0)]).unwrap();
+    let segment = chart.segment_mut(0).unwrap();
+    assert_eq!((segment.ratio, segment.value), (0.75, Some(30.0)));
+    assert_eq!(segment.label, "a");
+    assert_eq!(chart.segment_mut(1).unwrap().ratio, 0.25);
+    assert_eq!(chart.segment_mut(2).unwrap().ratio, 0.0);
+
+    assert_eq!(
+        PieChart::from_values(&[("a", 1.0), ("b", -1.0)]).err(),
+        Some(PieChartError::Negative(1))
+    );
+    assert_eq!(
+        PieChart::from_values(&[("a", f64::NAN)]).err(),
+        Some(PieChartError::NonFinite(0))
+    );
+    assert_eq!(
+        PieChart::from_values(&[("a", 0.0), ("b", 0.0)]).err(),
+        Some(PieChartError::AllZero)
+    );
+    assert_eq!(
+        PieChart::from_values::<&str>(&[]).err(),
+        Some(PieChartError::AllZero)
+    );
+}
+
+#[test]
+fn pie_chart_sum_policy() {
+    use svg_util::pie_chart::{PieChartError, SumPolicy};
+    let mut chart = PieChart::new();
+    let ratios = |chart: &mut PieChart| -> Vec<f64> {
+        (0..2)
+            .map(|i| chart.segment_mut(i).unwrap().ratio)
+            .collect()
+    };
+
+    chart
+        .try_set_segments(&[1.0, 3.0], SumPolicy::Normalize)
+        .unwrap();
+    assert_eq!(ratios(&mut chart), [0.25, 0.75]);
+
+    // A sum below one leaves a gap, above one is rejected.
+    chart.try_set_segments(&[0.2, 0.3], SumPolicy::Gap).unwrap();
+    assert_eq!(ratios(&mut chart), [0.2, 0.3]);
+    assert_eq!(
+        chart.try_set_segments(&[0.6, 0.6], SumPolicy::Gap),
+        Err(PieChartError::InvalidSum(1.2))
+    );
+
+    // Only a sum of one is accepted, a rejected call keeps the previous segments.
+    assert_eq!(
+        chart.try_set_segments(&[0.2, 0.3], SumPolicy::Error),
+        Err(PieChartError::InvalidSum(0.5))
+    );
+    assert_eq!(ratios(&mut chart), [0.2, 0.3]);
+    chart
+        .try_set_segments(&[0.4, 0.6], SumPolicy::Error)
+        .unwrap();
+    assert_eq!(ratios(&mut chart), [0.4, 0.6]);
+
+    assert_eq!(
+        chart.try_set_segments(&[0.5, -0.5], SumPolicy::Normalize),
+        Err(PieChartError::Negative(1))
+    );
+    assert_eq!(
+        chart.try_set_segments(&[0.0, 0.0], SumPolicy::Normalize),
+        Err(PieChartError::AllZero)
+    );
+}
+
+#[test]
+fn pie_chart_segment_value_builder() {
+    use svg_util::pie_chart::PieSegment;
+    let segment = PieSegment::new(0.25).value(250.0);
+    assert_eq!((segment.ratio, segment.value), (0.25, Some(250.0)));
+}
+
+/// The label and fill of every legend entry.
+fn legend_entries(legend: svg_util::legend::Legend) -> Vec<(String, String)> {
+    let svg = legend.svg();
+    let mut fill = None;
+    let mut out = vec![];
+    // Each entry is a swatch followed by its label.
+    for node in common::descendants(&svg) {
+        if node.get_name() == "rect" {
+            fill = common::attribute(node, "fill");
+        } else if common::is_text(node) {
+            out.push((common::text(node), fill.take().unwrap_or_default()));
+        }
+    }
+    out
+}
+
+#[test]
+fn pie_chart_legend_follows_original_segments() {
+    use svg_util::pie_chart::OtherBucket;
+    let mut chart = PieChart::new();
+    chart.set_segments(&[0.45, 0.02, 0.03, 0.5]);
+    chart.set_other(OtherBucket::threshold(0.05));
+    let entries = legend_entries(chart.legend(&["A", "B", "C", "D"]));
+    assert_eq!(
+        entries,
+        [
+            ("A".to_owned(), "#D81B60".to_owned()),
+            ("D".to_owned(), "#004D40".to_owned()),
+            ("Other".to_owned(), "#9E9E9E".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn pie_chart_legend_only_provided_labels() {
+    let mut chart = PieChart::new();
+    chart.set_segments(&[(0.5, "first"), (0.25, "second"), (0.25, "third")]);
+    let entries = legend_entries(chart.legend(&["A"]));
+    assert_eq!(entries, [("A".to_owned(), "#D81B60".to_owned())]);
+}
+
+#[test]
+fn pie_chart_sunburst_rings() {
+    use svg_util::pie_chart::{Sunburst, SunburstNode};
+    let mut sunburst = Sunburst::new();
+    sunburst.set_radius(90.0);
+    sunburst.set_inner_radius(30.0);
+    sunburst.add_root(
+        SunburstNode::new("a", 3.0)
+            .child(SunburstNode::new("a1", 1.0))
+            .child(SunburstNode::new("a2", 2.0)),
+    );
+    sunburst.add_root(SunburstNode::new("b", 1.0).child(SunburstNode::new("b1", 0.5)));
+
+    // Each sector as (inner radius, outer radius, start angle, swept angle), inner ring first.
+    let sectors: Vec<(f64, f64, f64, f64)> = common::elements(&sunburst.svg(), "path")
+        .into_iter()
+        .map(|path| {
+            let d = common::attribute(path, "d").unwrap();
+            let arcs = arcs(&Data::parse(&d).unwrap());
+            let outer = arcs.iter().find(|a| a.sweep).unwrap();
+            let inner = arcs.iter().find(|a| !a.sweep).unwrap();
+            let start = outer.start.1.atan2(outer.start.0).rem_euclid(2.0 * PI);
+            (inner.radius, outer.radius, start, outer.swept())
+        })
+        .collect();
+    assert_eq!(sectors.len(), 5);
+
+    let rings: Vec<(f64, f64)> = sectors.iter().map(|s| (s.0, s.1)).collect();
+    assert_eq!(
+        rings,
+        [
+            (30.0, 60.0),
+            (30.0, 60.0),
+            (60.0, 90.0),
+            (60.0, 90.0),
+            (60.0, 90.0)
+        ]
+    );
+
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-3;
+    let (a, b) = (sectors[0], sectors[1]);
+    assert!(close(a.3, 1.5 * PI) && close(b.3, 0.5 * PI));
+    // Children divide their parent's span by value, a partial sum leaves the rest empty.
+    for (child, parent, offset, angle) in [
+        (sectors[2], a, 0.0, 0.5 * PI),
+        (sectors[3], a, 0.5 * PI, PI),
+        (sectors[4], b, 0.0, 0.25 * PI),
+    ] {
+        let relative = (child.2 - parent.2).rem_euclid(2.0 * PI);
+        assert!(close(relative, offset), "{child:?} in {parent:?}");
+        assert!(close(child.3, angle), "{child:?} in {parent:?}");
+        assert!(relative + child.3 <= parent.3 + 1e-3);
+    }
+}
+
+#[test]
+fn pie_chart_end_before_start() {
+    let mut chart = PieChart::new();
+    chart.set_segments(&[0.5, 0.5]);
+    chart.set_end(PI / 2.0);
+    chart.set_start(-PI / 2.0, StartStyle::Edge);
+    let angles = chart.segment_angles();
+    assert!((angles[0].0 + PI / 2.0).abs() < 1e-9);
+    let end = angles[1].0 + angles[1].1;
+    assert!((end - PI / 2.0).abs() < 1e-9, "{end}");
+
+    // A sweep replaces the end.
+    chart.set_sweep(PI / 2.0);
+    let end = chart.segment_angles()[1].0 + chart.segment_angles()[1].1;
+    assert!(end.abs() < 1e-9, "{end}");
+}
+
+#[test]
+fn pie_chart_needle_fill() {
+    use svg_util::color::Color;
+    use svg_util::pie_chart::Needle;
+    let mut chart = PieChart::new();
+    chart.set_segments(&[1.0]);
+    chart.set_needle(Needle::new(0.5).fill(Color::rgb(255, 0, 0)));
+    let svg = chart.svg().to_string();
+    assert!(svg.contains("fill=\"#FF0000\""), "{svg}");
+    assert!(!svg.contains("fill=\"#FFFFFF\""), "{svg}");
+}
@@ -0,0 +1,321 @@
+mod common;
+
+use svg_util::plot::{format_ticks, AxisHorizontal, AxisVertical, Plot, Ticks};
+
+/// The text of all tick labels in the plot, in document order.
+fn labels(plot: &Plot) -> Vec<String> {
+    common::texts(&plot.svg())
+}
+
+fn frame_with_ticks(ticks: Ticks, minor: Option<usize>) -> Plot {
+    let h = AxisHorizontal::new(200.0);
+    h.set_canvas_range(0.0, 200.0);
+    h.set_plot_range(0.0, 1.0);
+    h.set_ticks(ticks);
+    if let Some(n) = minor {
+        h.set_minor_ticks(n);
+    }
+    let v = AxisVertical::new(100.0);
+    v.set_canvas_range(0.0, 100.0);
+    v.set_plot_range(0.0, 1.0);
+    v.set_ticks(Ticks::None);
+    Plot::new(&v.combine(&h))
+}
+
+#[test]
+fn plot_format_ticks_uneven_positions() {
+    assert_eq!(
+        format_ticks(&[0.0, 0.25, 0.5, 0.75, 1.0]),
+        ["0.00", "0.25", "0.50", "0.75", "1.00"]
+    );
+    assert_eq!(format_ticks(&[0.0, 1.0, 2.0]), ["0", "1", "2"]);
+    assert_eq!(format_ticks(&[-0.5, 0.0, 0.1]), ["-0.5", "0.0", "0.1"]);
+}
+
+#[test]
+fn plot_explicit_positions_keep_distinct_labels() {
+    let plot = frame_with_ticks(Ticks::Positions(vec![0.0, 0.25, 0.5, 0.75, 1.0]), None);
+    assert_eq!(labels(&plot), ["0.00", "0.25", "0.50", "0.75", "1.00"]);
+}
+
+#[test]
+fn plot_duplicate_positions_with_minor_ticks() {
+    // Used to loop forever, the spacing between the duplicates is zero.
+    let plot = frame_with_ticks(Ticks::Positions(vec![0.5, 0.5, 1.0]), Some(4));
+    assert_eq!(labels(&plot), ["0.5", "1.0"]);
+}
+
+#[test]
+fn plot_tiny_spacing_is_bounded() {
+    let plot = frame_with_ticks(Ticks::Positions(vec![0.5, 0.5 + 1e-12]), Some(10));
+    let svg = plot.svg().to_string();
+    // Minor ticks are capped instead of filling the whole range at this spacing.
+    assert!(svg.len() < 1_000_000);
+}
+
+#[test]
+fn plot_log_axis_with_non_positive_range() {
+    use svg_util::plot::Scale;
+    let v = AxisVertical::new(100.0);
+    v.set_canvas_range(0.0, 100.0);
+    v.set_scale(Scale::log10());
+    v.set_plot_range(0.0, 1000.0);
+    // The zero bound is replaced by one three decades below the upper bound.
+    assert!((v.project(1.0) - 100.0).abs() < 1e-9);
+    assert!((v.project(1000.0) - 0.0).abs() < 1e-9);
+    // Values a logarithm cannot map end up at the lower edge instead of at infinity.
+    assert!((v.project(0.0) - 100.0).abs() < 1e-9);
+    assert!((v.project(-5.0) - 100.0).abs() < 1e-9);
+
+    let h = AxisHorizontal::new(100.0);
+    let mut plot = Plot::new(&v.combine(&h));
+    plot.line_xy(&[(0.0, 0.0), (50.0, 10.0), (100.0, 100.0)]);
+    let svg = plot.svg().to_string();
+    assert!(!svg.contains("NaN") && !svg.contains("inf"), "{}", svg);
+}
+
+#[test]
+fn plot_invalid_scale_parameters_are_replaced() {
+    use svg_util::plot::Scale;
+    let v = AxisVertical::new(100.0);
+    v.set_canvas_range(0.0, 100.0);
+    v.set_plot_range(-10.0, 10.0);
+    v.set_scale(Scale::SymLog {
+        base: 1.0,
+        threshold: 0.0,
+    });
+    for value in [-10.0, 0.0, 5.0, 10.0] {
+        assert!(v.project(value).is_finite());
+    }
+}
+
+#[test]
+fn plot_log_labels_share_a_format() {
+    use svg_util::plot::Scale;
+    let labels = |scale: Scale, min: f64, max: f64| -> Vec<String> {
+        scale
+            .ticks(min, max, 10)
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect()
+    };
+    assert_eq!(labels(Scale::log2(), 1.0, 16.0), ["1", "2", "4", "8", "16"]);
+    // 32 needs an exponent, so all labels on the axis get one.
+    assert_eq!(
+        labels(Scale::log2(), 1.0, 32.0),
+        ["2^0", "2^1", "2^2", "2^3", "2^4", "2^5"]
+    );
+    assert_eq!(
+        labels(Scale::log10(), 0.001, 1000.0),
+        ["0.001", "0.01", "0.1", "1", "10", "100", "1000"]
+    );
+    assert_eq!(
+        labels(Scale::symlog(1.0), -1e5, 1e5),
+        [
+            "0", "10^0", "-10^0", "10^1", "-10^1", "10^2", "-10^2", "10^3", "-10^3", "10^4",
+            "-10^4", "10^5", "-10^5"
+        ]
+    );
+}
+
+#[test]
+fn plot_vertical_axis_points_up() {
+    let h = AxisHorizontal::new(100.0);
+    h.set_canvas_range(0.0, 100.0);
+    h.set_plot_range(-1.0, 3.0);
+    let v = AxisVertical::new(100.0);
+    v.set_canvas_range(0.0, 100.0);
+    v.set_plot_range(-1.0, 3.0);
+    let mut plot = Plot::new(&v.combine(&h));
+    let line = plot.line_xy(&[(-1.0, 3.0), (3.0, -1.0), (1.0, 1.0)]);
+
+    // Svg's y axis points down, so the maximum is at the top of the canvas.
+    let group = line.svg();
+    let polyline = common::elements(&group, "polyline")[0];
+    assert_eq!(
+        common::attribute(polyline, "points").unwrap().trim(),
+        "0,0 100,100 50,50"
+    );
+}
+
+/// The offset of a node translated with a single translate().
+fn translation(node: &dyn svg::Node) -> (f64, f64) {
+    let transform = common::attribute(node, "transform").unwrap_or_default();
+    let args = transform
+        .trim()
+        .strip_prefix("translate(")
+        .and_then(|t| t.strip_suffix(')'))
+        .expect("a single translation");
+    let values: Vec<f64> = args
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|a| !a.is_empty())
+        .map(|a| a.parse().unwrap())
+        .collect();
+    (values[0], values.get(1).copied().unwrap_or(0.0))
+}
+
+#[test]
+fn plot_axes_cross_at_the_origin() {
+    let h = AxisHorizontal::new(200.0);
+    h.set_canvas_range(0.0, 200.0);
+    h.set_plot_range(-1.0, 3.0);
+    let v = AxisVertical::new(100.0);
+    v.set_canvas_range(0.0, 100.0);
+    v.set_plot_range(-2.0, 2.0);
+    let plot = Plot::new(&v.combine(&h));
+
+    let svg = plot.svg();
+    let frame = &svg.get_children()[0];
+    let axes = frame.get_children().unwrap();
+    // The horizontal axis moves to y = 0, the vertical axis to x = 0.
+    assert_eq!(translation(axes[0].as_ref()), (0.0, 50.0));
+    assert_eq!(translation(axes[1].as_ref()), (50.0, 0.0));
+}
+
+/// The vertices of a path, in order.
+fn vertices(path: &dyn svg::Node) -> Vec<(f64, f64)> {
+    use svg::node::element::path::{Command, Data};
+    let data = Data::parse(&common::attribute(path, "d").unwrap()).unwrap();
+    data.iter()
+        .filter_map(|command| match command {
+            Command::Move(_, p) | Command::Line(_, p) => Some((p[0] as f64, p[1] as f64)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn plot_marker_shapes() {
+    use svg::node::element::Rectangle;
+    use svg_util::plot::Marker;
+    let (x, y, size) = (30.0, 40.0, 10.0);
+    let number = |node: &dyn svg::Node, name: &str| -> f64 {
+        common::attribute(node, name).unwrap().parse().unwrap()
+    };
+    let centroid = |points: &[(f64, f64)]| {
+        let n = points.len() as f64;
+        let (sx, sy) = points
+            .iter()
+            .fold((0.0, 0.0), |(sx, sy), (px, py)| (sx + px, sy + py));
+        (sx / n, sy / n)
+    };
+    let close = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4;
+
+    let circle = Marker::Circle.svg(x, y, size);
+    assert_eq!(circle.get_name(), "circle");
+    assert_eq!(
+        (
+            number(&*circle, "cx"),
+            number(&*circle, "cy"),
+            number(&*circle, "r")
+        ),
+        (x, y, 5.0)
+    );
+
+    let square = Marker::Square.svg(x, y, size);
+    assert_eq!(square.get_name(), "rect");
+    assert_eq!((number(&*square, "x"), number(&*square, "y")), (25.0, 35.0));
+    assert_eq!(
+        (number(&*square, "width"), number(&*square, "height")),
+        (10.0, 10.0)
+    );
+
+    // Path markers are centered on the average of their vertices.
+    for (marker, count) in [
+        (Marker::Triangle, 3),
+        (Marker::Cross, 4),
+        (Marker::Diamond, 4),
+    ] {
+        let node = marker.svg(x, y, size);
+        assert_eq!(node.get_name(), "path", "{marker:?}");
+        let points = vertices(&*node);
+        assert_eq!(points.len(), count, "{marker:?}");
+        assert!(close(centroid(&points), (x, y)), "{marker:?}: {points:?}");
+        // The size is the width of the marker.
+        let (min, max) = points.iter().fold((f64::MAX, f64::MIN), |(min, max), p| {
+            (min.min(p.0), max.max(p.0))
+        });
+        assert!((max - min - size).abs() < 1e-4, "{marker:?}: {points:?}");
+    }
+
+    let node = Marker::Node(Box::new(Rectangle::new())).svg(x, y, size);
+    assert_eq!(node.get_name(), "g");
+    assert_eq!(
+        common::attribute(&*node, "transform").as_deref(),
+        Some("translate(30,40) scale(10)")
+    );
+    assert_eq!(common::elements(&*node, "rect").len(), 1);
+}
+
+/// A plot with three categories 100 wide and a vertical range of -10 to 10 over 100 pixels.
+fn category_plot() -> Plot {
+    let h = AxisHorizontal::new(300.0);
+    h.set_canvas_range(0.0, 300.0);
+    h.set_categories(&["a", "b", "c"]);
+    let v = AxisVertical::new(100.0);
+    v.set_canvas_range(0.0, 100.0);
+    v.set_plot_range(-10.0, 10.0);
+    Plot::new(&v.combine(&h))
+}
+
+/// The x, y, width and height of each bar in the series.
+fn bars(series: &svg_util::plot::DrawElementHandle) -> Vec<[f64; 4]> {
+    let group = series.svg();
+    common::elements(&group, "rect")
+        .into_iter()
+        .map(|rect| {
+            ["x", "y", "width", "height"].map(|name| {
+                let v: f64 = common::attribute(rect, name).unwrap().parse().unwrap();
+                (v * 1e6).round() / 1e6
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn plot_bars_grouped_offsets() {
+    let mut plot = category_plot();
+    let series = plot.bars_grouped(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+    // The bar width of 0.8 categories is shared, each series gets its own slot.
+    assert_eq!(
+        bars(&series[0]),
+        [
+            [10.0, 45.0, 40.0, 5.0],
+            [110.0, 40.0, 40.0, 10.0],
+            [210.0, 35.0, 40.0, 15.0]
+        ]
+    );
+    assert_eq!(
+        bars(&series[1]),
+        [
+            [50.0, 30.0, 40.0, 20.0],
+            [150.0, 25.0, 40.0, 25.0],
+            [250.0, 20.0, 40.0, 30.0]
+        ]
+    );
+}
+
+#[test]
+fn plot_bars_stacked_on_previous() {
+    let mut plot = category_plot();
+    let series = plot.bars_stacked(&[&[2.0, -1.0], &[3.0, -2.0]]);
+    // Positive values stack upwards from zero, negative values downwards.
+    assert_eq!(
+        bars(&series[0]),
+        [[10.0, 40.0, 80.0, 10.0], [110.0, 50.0, 80.0, 5.0]]
+    );
+    assert_eq!(
+        bars(&series[1]),
+        [[10.0, 25.0, 80.0, 15.0], [110.0, 55.0, 80.0, 10.0]]
+    );
+}
+
+#[test]
+fn plot_bar_negative_value() {
+    let mut plot = category_plot();
+    let series = plot.bar(&[-4.0, 0.0]);
+    assert_eq!(
+        bars(&series),
+        [[10.0, 50.0, 80.0, 20.0], [110.0, 50.0, 80.0, 0.0]]
+    );
+}
@@ -0,0 +1,136 @@
+use svg::Node;
+use svg_util::color::Color;
+use svg_util::tab::{Corner, Tab, TabEdge};
+
+#[test]
+fn tab_label_contrasts_with_fill() {
+    let tab = Tab::new()
+        .sized(50.0, 80.0)
+        .tab(15.0, 25.0)
+        .tab_edge(TabEdge::Left)
+        .tab_position(10.0);
+    let label = tab.label("A", Color::from_hex(0x004D40));
+    let attributes = label.get_attributes().unwrap();
+    assert_eq!(attributes["fill"].to_string(), "#FFFFFF");
+    assert_eq!(attributes["x"].to_string(), "-7.5");
+    assert_eq!(attributes["y"].to_string(), "22.5");
+
+    let label = tab.label("A", Color::from_hex(0xFFC107));
+    assert_eq!(
+        label.get_attributes().unwrap()["fill"].to_string(),
+        "#000000"
+    );
+}
+
+/// The path data of a tab with the default round corners on the provided edge.
+fn round_tab(edge: TabEdge) -> String {
+    let tab = Tab::new()
+        .sized(50.0, 80.0)
+        .radius(5.0)
+        .tab(15.0, 25.0)
+        .tab_edge(edge)
+        .tab_position(10.0);
+    tab.svg().get_attributes().unwrap()["d"].to_string()
+}
+
+#[test]
+fn tab_round_left() {
+    assert_eq!(
+        round_tab(TabEdge::Left),
+        "M5,0 L45,0 A5,5,0,0,1,50,5 L50,75 A5,5,0,0,1,45,80 L5,80 A5,5,0,0,1,0,75 L0,40 \
+         A5,5,0,0,0,-5,35 L-10,35 A5,5,0,0,1,-15,30 L-15,15 A5,5,0,0,1,-10,10 L-5,10 \
+         A5,5,0,0,0,0,5 A5,5,0,0,1,5,0 z"
+    );
+}
+
+#[test]
+fn tab_round_right() {
+    assert_eq!(
+        round_tab(TabEdge::Right),
+        "M5,0 L45,0 A5,5,0,0,1,50,5 A5,5,0,0,0,55,10 L60,10 A5,5,0,0,1,65,15 L65,30 \
+         A5,5,0,0,1,60,35 L55,35 A5,5,0,0,0,50,40 L50,75 A5,5,0,0,1,45,80 L5,80 \
+         A5,5,0,0,1,0,75 L0,5 A5,5,0,0,1,5,0 z"
+    );
+}
+
+#[test]
+fn tab_round_top() {
+    assert_eq!(
+        round_tab(TabEdge::Top),
+        "M5,0 A5,5,0,0,0,10,-5 L10,-20 A5,5,0,0,1,15,-25 L20,-25 A5,5,0,0,1,25,-20 L25,-5 \
+         A5,5,0,0,0,30,0 L45,0 A5,5,0,0,1,50,5 L50,75 A5,5,0,0,1,45,80 L5,80 \
+         A5,5,0,0,1,0,75 L0,5 A5,5,0,0,1,5,0 z"
+    );
+}
+
+#[test]
+fn tab_round_bottom() {
+    assert_eq!(
+        round_tab(TabEdge::Bottom),
+        "M5,0 L45,0 A5,5,0,0,1,50,5 L50,75 A5,5,0,0,1,45,80 L30,80 A5,5,0,0,0,25,85 \
+         L25,100 A5,5,0,0,1,20,105 L15,105 A5,5,0,0,1,10,100 L10,85 A5,5,0,0,0,5,80 \
+         A5,5,0,0,1,0,75 L0,5 A5,5,0,0,1,5,0 z"
+    );
+}
+
+#[test]
+fn tab_round_none() {
+    assert_eq!(
+        round_tab(TabEdge::None),
+        "M5,0 L45,0 A5,5,0,0,1,50,5 L50,75 A5,5,0,0,1,45,80 L5,80 A5,5,0,0,1,0,75 L0,5 \
+         A5,5,0,0,1,5,0 z"
+    );
+}
+
+#[test]
+fn tab_set_radius() {
+    let mut tab = Tab::new().sized(50.0, 80.0).tab(15.0, 25.0);
+    tab.set_radius(5.0);
+    assert_eq!(
+        tab,
+        Tab::new().sized(50.0, 80.0).tab(15.0, 25.0).radius(5.0)
+    );
+    assert!(tab.body_corners.iter().all(|c| c.radius == 5.0));
+    assert!(tab.fillet_corners.iter().all(|c| c.radius == 5.0));
+}
+
+/// A tab on the left edge, without any corners set.
+fn left_tab() -> Tab {
+    Tab::new()
+        .sized(50.0, 80.0)
+        .tab(15.0, 25.0)
+        .tab_edge(TabEdge::Left)
+        .tab_position(10.0)
+}
+
+#[test]
+fn tab_chamfer() {
+    let tab = left_tab().corners(Corner::chamfer(5.0));
+    assert_eq!(
+        tab.svg().get_attributes().unwrap()["d"].to_string(),
+        "M5,0 L45,0 L50,5 L50,75 L45,80 L5,80 L0,75 L0,40 L-5,35 L-10,35 L-15,30 L-15,15 \
+         L-10,10 L-5,10 L0,5 L5,0 z"
+    );
+}
+
+#[test]
+fn tab_sharp() {
+    let tab = left_tab().corners(Corner::sharp());
+    assert_eq!(
+        tab.svg().get_attributes().unwrap()["d"].to_string(),
+        "M0,0 L50,0 L50,80 L0,80 L0,35 L-15,35 L-15,10 L0,10 L0,0 z"
+    );
+}
+
+#[test]
+fn tab_round_tab_on_sharp_body() {
+    let tab = left_tab()
+        .body_corners(Corner::sharp())
+        .tab_corners(Corner::round(5.0))
+        .fillet_corners(Corner::sharp());
+    assert_eq!(
+        tab.svg().get_attributes().unwrap()["d"].to_string(),
+        "M0,0 L50,0 L50,80 L0,80 L0,35 L-10,35 A5,5,0,0,1,-15,30 L-15,15 A5,5,0,0,1,-10,10 \
+         L0,10 L0,0 z"
+    );
+}
@@ -0,0 +1,132 @@
+use svg_util::transform::Affine;
+
+fn assert_close(a: &Affine, b: &Affine) {
+    let (x, y) = (
+        [a.a, a.b, a.c, a.d, a.e, a.f],
+        [b.a, b.b, b.c, b.d, b.e, b.f],
+    );
+    for (p, q) in x.iter().zip(y.iter()) {
+        assert!((p - q).abs() < 1e-6, "{a:?} vs {b:?}");
+    }
+}
+
+/// Parse, format and parse again, the result should be the same transform.
+fn round_trip(s: &str) -> String {
+    let parsed = Affine::parse(s).unwrap();
+    let formatted = parsed.to_string();
+    assert_close(&Affine::parse(&formatted).unwrap(), &parsed);
+    formatted
+}
+
+#[test]
+fn transform_round_trip_translate() {
+    assert_eq!(round_trip("translate(10,20)"), "translate(10,20)");
+    assert_eq!(round_trip("translate(10)"), "translate(10)");
+    assert_eq!(round_trip("translate(-2.5 0.125)"), "translate(-2.5,0.125)");
+}
+
+#[test]
+fn transform_round_trip_scale() {
+    assert_eq!(round_trip("scale(2)"), "scale(2)");
+    assert_eq!(round_trip("scale(2,3)"), "scale(2,3)");
+    assert_eq!(round_trip("scale(1,-1)"), "scale(1,-1)");
+}
+
+#[test]
+fn transform_round_trip_rotate() {
+    assert_eq!(round_trip("rotate(45)"), "rotate(45)");
+    assert_eq!(round_trip("rotate(30,10,20)"), "rotate(30,10,20)");
+    assert_close(
+        &Affine::parse("rotate(90,10,0)").unwrap(),
+        &(Affine::translate(10.0, 0.0) * Affine::rotate(90.0) * Affine::translate(-10.0, 0.0)),
+    );
+}
+
+#[test]
+fn transform_round_trip_skew() {
+    assert_eq!(round_trip("skewX(30)"), "skewX(30)");
+    assert_eq!(round_trip("skewY(-15)"), "skewY(-15)");
+}
+
+#[test]
+fn transform_round_trip_matrix() {
+    assert_eq!(round_trip("matrix(1,2,3,4,5,6)"), "matrix(1,2,3,4,5,6)");
+    // A matrix that is a plain translation is written as one.
+    assert_eq!(round_trip("matrix(1 0 0 1 5 6)"), "translate(5,6)");
+    assert_eq!(round_trip("matrix(1,0,0,1,0,0)"), "");
+    round_trip("translate(10,20) rotate(30) scale(2,0.5) skewX(10)");
+}
+
+#[test]
+fn transform_parse_list_order() {
+    // The last transform in the list is applied first.
+    let t = Affine::parse("translate(10,0) scale(2)").unwrap();
+    assert_eq!(t.apply((1.0, 1.0)), (12.0, 2.0));
+    assert_eq!(Affine::parse(""), Ok(Affine::IDENTITY));
+}
+
+#[test]
+fn transform_parse_malformed() {
+    for s in [
+        "translate(10,20",
+        "translate 10 20",
+        "rotate(1,2)",
+        "scale()",
+        "matrix(1,2,3,4,5)",
+        "skewX(a)",
+        "shear(10)",
+        "translate(1,2) junk",
+    ] {
+        assert!(Affine::parse(s).is_err(), "{s}");
+    }
+}
+
+#[test]
+fn transform_invert() {
+    let a = Affine::parse("translate(10,20) rotate(30) scale(2,0.5) skewX(10)").unwrap();
+    let inverse = a.invert().unwrap();
+    assert_close(&(a * inverse), &Affine::IDENTITY);
+    assert_close(&(inverse * a), &Affine::IDENTITY);
+
+    // Singular transforms collapse the plane, they can't be undone.
+    assert_eq!(Affine::scale(0.0, 1.0).invert(), None);
+    assert_eq!(Affine::new(1.0, 2.0, 2.0, 4.0, 5.0, 6.0).invert(), None);
+}
+
+#[test]
+fn transform_decompose_round_trip() {
+    for s in [
+        "translate(10,20) rotate(30) skewX(15) scale(2,0.5)",
+        "rotate(-120) scale(3)",
+        "scale(1,-1)",
+        "matrix(1,2,3,4,5,6)",
+    ] {
+        let a = Affine::parse(s).unwrap();
+        assert_close(&a.decompose().affine(), &a);
+    }
+    let parts = Affine::parse("translate(10,20) rotate(30) skewX(15) scale(2,0.5)")
+        .unwrap()
+        .decompose();
+    assert_eq!(parts.translate, (10.0, 20.0));
+    assert!((parts.rotation - 30.0).abs() < 1e-9);
+    assert!((parts.skew_x - 15.0).abs() < 1e-9);
+    assert!((parts.scale.0 - 2.0).abs() < 1e-9 && (parts.scale.1 - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn transform_apply_bounds_rotated() {
+    // A 10 by 20 box rotated a quarter turn about its center keeps its center.
+    let rotated = Affine::rotate_about(90.0, 5.0, 10.0);
+    let ((min_x, min_y), (max_x, max_y)) = rotated.apply_bounds((0.0, 0.0), (10.0, 20.0));
+    for (v, expected) in [(min_x, -5.0), (min_y, 5.0), (max_x, 15.0), (max_y, 15.0)] {
+        assert!((v - expected).abs() < 1e-9, "{v} vs {expected}");
+    }
+
+    // At 45 degrees the corners stick out along the diagonals.
+    let half = 5.0 * 2f64.sqrt();
+    let ((min_x, min_y), (max_x, max_y)) =
+        Affine::rotate(45.0).apply_bounds((-5.0, -5.0), (5.0, 5.0));
+    for (v, expected) in [(min_x, -half), (min_y, -half), (max_x, half), (max_y, half)] {
+        assert!((v - expected).abs() < 1e-9, "{v} vs {expected}");
+    }
+}
@@ -0,0 +1,42 @@
+use svg::node::element::Group;
+use svg::Node;
+use svg_util::transform::Transformed;
+
+#[test]
+fn transformed_composes() {
+    let mut group = Group::new().translated_xy(80, 0).rotated(45);
+    assert_eq!(
+        group.get_attributes().unwrap()["transform"].to_string(),
+        "translate(80,0) rotate(45)"
+    );
+    assert_eq!(
+        group.transform().as_deref(),
+        Some("translate(80,0) rotate(45)")
+    );
+
+    group.reset_transform();
+    assert!(!group.get_attributes().unwrap().contains_key("transform"));
+    assert_eq!(group.transform(), None);
+    // After a reset, the list starts over.
+    assert_eq!(group.rotated(10).transform().as_deref(), Some("rotate(10)"));
+}
+
+#[test]
+fn transformed_helpers() {
+    let cases = [
+        (Group::new().scaled(2), "scale(2)"),
+        (Group::new().scaled_xy(2, 0.5), "scale(2,0.5)"),
+        (Group::new().skewed_x(10), "skewX(10)"),
+        (Group::new().skewed_y(-5), "skewY(-5)"),
+        (Group::new().rotated_about(90, 10, 20), "rotate(90,10,20)"),
+        (Group::new().flipped_horizontal(), "scale(-1,1)"),
+        (Group::new().flipped_vertical(), "scale(1,-1)"),
+        (
+            Group::new().with_matrix(1.0, 0.5, 0.0, 1.0, 3.0, 4.0),
+            "matrix(1,0.5,0,1,3,4)",
+        ),
+    ];
+    for (group, expected) in cases {
+        assert_eq!(group.transform().as_deref(), Some(expected));
+    }
+}